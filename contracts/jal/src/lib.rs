@@ -1,184 +1,6351 @@
+// Anchor 0.31's generated IDL handlers still call the deprecated `AccountInfo::realloc`.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as sysvar_instructions, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{
+    self, Approve, Burn, FreezeAccount, InitializeMint2, Mint, MintTo, Revoke, SetAuthority, Token,
+    TokenAccount, Transfer,
+};
+use std::ops::RangeInclusive;
 
 declare_id!("Fg6PaFpoVXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+// Meter ids double as PDA seeds, which are capped at 32 bytes
+const MAX_METER_ID_LEN: usize = 32;
+const SECONDS_PER_DAY: i64 = 86_400;
+const BPS_DENOMINATOR: u64 = 10_000;
+const LITERS_PER_M3: u64 = 1_000;
+// US gallon in nanoliters
+const NANOLITERS_PER_GALLON: u128 = 3_785_411_784;
+// Domain tag prefixed to every message an oracle signs for a reading
+const READING_ATTESTATION_DOMAIN: &[u8] = b"JAL_READING_V1";
+// Domain tag prefixed to every payload a meter device signs
+const DEVICE_PAYLOAD_DOMAIN: &[u8] = b"JAL_METER_V1";
+const DEFAULT_MAX_CLOCK_DRIFT_SECS: i64 = 300;
+const DEFAULT_SETTLEMENT_WINDOW_SECS: i64 = 3 * 86_400;
+// 1 billion whole JAL, scaled by the mint's decimals at init
+const TOTAL_SUPPLY_TOKENS: u64 = 1_000_000_000;
+const SECONDS_PER_YEAR: i64 = 365 * SECONDS_PER_DAY;
+// Whitepaper: team tokens are locked for at least a year
+const MIN_TEAM_CLIFF_SECS: i64 = SECONDS_PER_YEAR;
+const MAX_BENEFICIARY_NAME_LEN: usize = 64;
+const MAX_RETIREMENT_REASON_LEN: usize = 128;
+const MAX_RETIREMENT_SOURCES: usize = 8;
+const MAX_RETIREMENT_BATCHES: usize = 8;
+const MAX_DISTRICT_CUSTOMERS: usize = 32;
+const MAX_ASSOCIATION_NAME_LEN: usize = 64;
+const MAX_LEADERBOARD_TOP_N: u8 = 10;
+// 10^19 overflows u64
+const MAX_REGISTER_DIGITS: u8 = 18;
+// A register drop only counts as a rollover from the top tenth of its range
+const REGISTER_WRAP_WINDOW_DIVISOR: u64 = 10;
+
 #[program]
 pub mod jal_program {
     use super::*;
 
-    // === ONLY ADMIN CAN RECORD ===
-    pub fn record_reading(
-        ctx: Context<RecordReading>,
-        meter_id: String,
-        reading: u64,
-        meter_type: MeterType,
+    // === ONE-SHOT BOOTSTRAP: CONFIG + JAL MINT ===
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        residential_threshold: u64,
+        municipal_multiplier: u8,
+        jal_per_m3_bps: u32,
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
     ) -> Result<()> {
-        let reading_acc = &mut ctx.accounts.reading;
-        let clock = Clock::get()?;
-        let config = &ctx.accounts.config;
+        // === CREATE THE MINT AT ITS PDA ===
+        let mint_seeds = &[b"mint".as_ref(), &[ctx.bumps.mint]];
+        let signer = &[&mint_seeds[..]];
+        let system = ctx.accounts.system_program.to_account_info();
+        let admin = ctx.accounts.admin.to_account_info();
+        let mint = ctx.accounts.mint.to_account_info();
+        let rent = Rent::get()?.minimum_balance(Mint::LEN);
 
-        // === VALIDATE ===
-        require!(meter_id.len() <= 50, CustomError::InvalidMeterId);
-        require!(reading > 0, CustomError::InvalidReading);
+        if mint.lamports() == 0 {
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    system.clone(),
+                    CreateAccount {
+                        from: admin,
+                        to: mint.clone(),
+                    },
+                    signer,
+                ),
+                rent,
+                Mint::LEN as u64,
+                &ctx.accounts.token_program.key(),
+            )?;
+        } else {
+            // Anyone can send lamports to the PDA first, which makes `create_account`
+            // fail; top it up to rent-exempt and allocate + assign it instead
+            let shortfall = rent.saturating_sub(mint.lamports());
+            if shortfall > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        system.clone(),
+                        system_program::Transfer {
+                            from: admin,
+                            to: mint.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    system.clone(),
+                    Allocate {
+                        account_to_allocate: mint.clone(),
+                    },
+                    signer,
+                ),
+                Mint::LEN as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    system,
+                    Assign {
+                        account_to_assign: mint,
+                    },
+                    signer,
+                ),
+                &ctx.accounts.token_program.key(),
+            )?;
+        }
+
+        // === MINT AUTHORITY IS ALWAYS THE PDA, FREEZE IS CHOSEN HERE ===
+        token::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            decimals,
+            &ctx.accounts.mint_auth.key(),
+            freeze_authority.as_ref(),
+        )?;
 
-        // === ONLY ADMIN CAN CALL THIS FUNCTION ===
+        // === STORE CONFIG ===
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.residential_threshold = residential_threshold;
+        config.municipal_multiplier = municipal_multiplier;
+        config.jal_per_m3_bps = jal_per_m3_bps;
+        config.mint = ctx.accounts.mint.key();
+        config.bump = ctx.bumps.config;
+        config.mint_auth_bump = ctx.bumps.mint_auth;
+        config.max_clock_drift_secs = DEFAULT_MAX_CLOCK_DRIFT_SECS;
+        config.settlement_window_secs = DEFAULT_SETTLEMENT_WINDOW_SECS;
+
+        emit!(Initialized {
+            admin: config.admin,
+            mint: config.mint,
+            decimals,
+            freeze_authority,
+            residential_threshold,
+            municipal_multiplier,
+            jal_per_m3_bps,
+        });
+        Ok(())
+    }
+
+    // === SUPER-ADMIN FIXES THE SUPPLY CAP AND ALLOCATION BUCKETS ===
+    pub fn initialize_tokenomics(ctx: Context<InitializeTokenomics>) -> Result<()> {
         require!(
-            ctx.accounts.admin.key() == config.admin,
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
             CustomError::Unauthorized
         );
 
-        // === STORE ===
-        reading_acc.meter_id = meter_id.clone();
-        reading_acc.reading = reading;
-        reading_acc.timestamp = clock.unix_timestamp;
-        reading_acc.meter_type = meter_type;
-        reading_acc.authority = ctx.accounts.admin.key();
+        let total_cap = 10u64
+            .checked_pow(ctx.accounts.mint.decimals as u32)
+            .and_then(|scale| scale.checked_mul(TOTAL_SUPPLY_TOKENS))
+            .ok_or(CustomError::SupplyCapExceeded)?;
 
-        // === AUTO MINT LOGIC ===
-        if let Some(prev) = &ctx.accounts.previous_reading {
-            if prev.meter_id == meter_id && reading > prev.reading {
-                let saved = reading - prev.reading;
-
-                let mint_amount = match meter_type {
-                    MeterType::Residential => {
-                        let threshold = config.residential_threshold;
-                        if saved >= threshold {
-                            Some(saved * config.jal_per_liter)
-                        } else {
-                            None
-                        }
-                    }
-                    MeterType::Municipal => {
-                        let effective = (saved as u128) * config.municipal_multiplier as u128 / 100;
-                        Some(effective as u64 * config.jal_per_liter)
-                    }
-                    MeterType::Industrial => {
-                        None // No mint
-                    }
-                };
+        let tokenomics = &mut ctx.accounts.tokenomics;
+        tokenomics.total_cap = total_cap;
+        tokenomics.buckets = AllocationBucket::split(total_cap);
+        tokenomics.bump = ctx.bumps.tokenomics;
 
-                if let Some(amount) = mint_amount {
-                    if amount > 0 {
-                        mint_jal(&ctx, amount)?;
-                    }
-                }
-            }
-        }
+        emit!(TokenomicsInitialized {
+            total_cap,
+            allocations: tokenomics.buckets.map(|b| b.allocation),
+        });
+        Ok(())
+    }
+
+    // === SUPER-ADMIN MINTS FROM A NON-REWARD BUCKET (RESERVE, ECOSYSTEM, ...) ===
+    pub fn mint_allocation(
+        ctx: Context<MintAllocation>,
+        bucket: AllocationBucket,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
+        // A global pause stops every path that brings new JAL into circulation
+        require!(!ctx.accounts.config.paused, CustomError::ProgramPaused);
+        // Community rewards only leave through verified savings, team tokens through vesting
+        require!(
+            bucket != AllocationBucket::CommunityRewards && bucket != AllocationBucket::Team,
+            CustomError::BucketNotManuallyMintable
+        );
+        require!(amount > 0, CustomError::InvalidAmount);
 
+        ctx.accounts.tokenomics.charge(bucket, amount)?;
+        mint_jal(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_auth,
+            ctx.accounts.config.mint_auth_bump,
+            ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+
+        emit!(AllocationMinted {
+            bucket,
+            amount,
+            destination: ctx.accounts.destination.key(),
+            remaining: ctx.accounts.tokenomics.remaining(bucket),
+        });
         Ok(())
     }
 
-    // === ADMIN UPDATES CONFIG ===
-    pub fn update_config(
-        ctx: Context<UpdateConfig>,
-        residential_threshold: u64,
-        municipal_multiplier: u8,
-        jal_per_liter: u64,
+    // === VIEW: UNMINTED HEADROOM LEFT IN A BUCKET ===
+    pub fn bucket_headroom(ctx: Context<BucketHeadroom>, bucket: AllocationBucket) -> Result<u64> {
+        Ok(ctx.accounts.tokenomics.remaining(bucket))
+    }
+
+    // === SUPER-ADMIN ESCROWS TEAM TOKENS UNDER A VESTING SCHEDULE ===
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        schedule_id: u64,
+        beneficiary: Pubkey,
+        params: VestingParams,
     ) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-        require!(ctx.accounts.admin.key() == config.admin, CustomError::Unauthorized);
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
+        require!(!ctx.accounts.config.paused, CustomError::ProgramPaused);
+        require!(params.total_amount > 0, CustomError::InvalidAmount);
+        require!(params.cliff_secs >= MIN_TEAM_CLIFF_SECS, CustomError::InvalidVestingSchedule);
+        require!(
+            params.duration_secs > 0
+                && params.period_secs > 0
+                && params.period_secs <= params.duration_secs,
+            CustomError::InvalidVestingSchedule
+        );
+        // A backdated start would let the cliff elapse before the schedule exists
+        require!(
+            params.start_ts >= Clock::get()?.unix_timestamp,
+            CustomError::InvalidVestingSchedule
+        );
 
-        config.residential_threshold = residential_threshold;
-        config.municipal_multiplier = municipal_multiplier;
-        config.jal_per_liter = jal_per_liter;
-        config.admin = ctx.accounts.admin.key();
+        // Team tokens are minted straight into escrow
+        ctx.accounts
+            .tokenomics
+            .charge(AllocationBucket::Team, params.total_amount)?;
+        mint_jal(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_auth,
+            ctx.accounts.config.mint_auth_bump,
+            ctx.accounts.vault.to_account_info(),
+            params.total_amount,
+        )?;
+
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.beneficiary = beneficiary;
+        vesting.schedule_id = schedule_id;
+        vesting.vault = ctx.accounts.vault.key();
+        vesting.total_amount = params.total_amount;
+        vesting.claimed_amount = 0;
+        vesting.start_ts = params.start_ts;
+        vesting.cliff_secs = params.cliff_secs;
+        vesting.duration_secs = params.duration_secs;
+        vesting.period_secs = params.period_secs;
+        vesting.revocable = params.revocable;
+        vesting.revoked = false;
+        vesting.bump = ctx.bumps.vesting;
 
+        emit!(VestingScheduleCreated {
+            vesting: vesting.key(),
+            beneficiary,
+            schedule_id,
+            params,
+        });
         Ok(())
     }
-}
 
-// === MINT HELPER ===
-fn mint_jal(ctx: &Context<RecordReading>, amount: u64) -> Result<()> {
-    let seeds = &[b"mint_auth".as_ref(), &[ctx.bumps.mint_auth]];
-    let signer = &[&seeds];
+    // === BENEFICIARY PULLS WHATEVER HAS VESTED SO FAR ===
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+        let amount = vesting.releasable(now);
+        require!(amount > 0, CustomError::NothingToClaim);
 
-    token::mint_to(
-        CpiContext::new_with_signer(
+        let id_bytes = vesting.schedule_id.to_le_bytes();
+        let seeds = &[
+            b"vesting".as_ref(),
+            vesting.beneficiary.as_ref(),
+            id_bytes.as_ref(),
+            &[vesting.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vesting.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.claimed_amount += amount;
+
+        emit!(VestedClaimed {
+            vesting: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            amount,
+            claimed_amount: vesting.claimed_amount,
+        });
+        Ok(())
+    }
+
+    // === SUPER-ADMIN CLAWS BACK THE UNVESTED REMAINDER ===
+    pub fn revoke_unvested(ctx: Context<RevokeUnvested>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &ctx.accounts.vesting;
+        require!(vesting.revocable && !vesting.revoked, CustomError::VestingNotRevocable);
+
+        let vested = vesting.vested_amount(now);
+        let unvested = vesting.total_amount - vested;
+
+        if unvested > 0 {
+            let id_bytes = vesting.schedule_id.to_le_bytes();
+            let seeds = &[
+                b"vesting".as_ref(),
+                vesting.beneficiary.as_ref(),
+                id_bytes.as_ref(),
+                &[vesting.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            // Never circulated, so burn it and hand the headroom back to the team bucket
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.vault.to_account_info(),
+                        authority: ctx.accounts.vesting.to_account_info(),
+                    },
+                    signer,
+                ),
+                unvested,
+            )?;
+            ctx.accounts.tokenomics.release(AllocationBucket::Team, unvested);
+        }
+
+        let vesting = &mut ctx.accounts.vesting;
+        vesting.total_amount = vested;
+        vesting.revoked = true;
+
+        emit!(VestingRevoked {
+            vesting: vesting.key(),
+            beneficiary: vesting.beneficiary,
+            vested,
+            unvested,
+        });
+        Ok(())
+    }
+
+    // === ANY HOLDER IRREVERSIBLY RETIRES JAL AGAINST A NAMED BENEFICIARY ===
+    pub fn retire(ctx: Context<Retire>, params: RetireParams) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(params.amount > 0, CustomError::InvalidAmount);
+        require!(
+            params.beneficiary_name.len() <= MAX_BENEFICIARY_NAME_LEN
+                && params.reason.len() <= MAX_RETIREMENT_REASON_LEN
+                && params.sources.len() <= MAX_RETIREMENT_SOURCES
+                && params.batches.len() <= MAX_RETIREMENT_BATCHES,
+            CustomError::InvalidRetirement
+        );
+        require!(
+            params.vintage_start <= params.vintage_end && params.vintage_end <= now,
+            CustomError::InvalidRetirement
+        );
+
+        // === DRAW DOWN THE REFERENCED VINTAGE BATCHES ===
+        let mut liters = 0;
+        if !params.batches.is_empty() {
+            liters = draw_down_batches(
+                ctx.remaining_accounts,
+                &params.batches,
+                params.amount,
+                vintage_year(params.vintage_start)..=vintage_year(params.vintage_end),
+            )?;
+        }
+
+        // Burning is what makes the claim exclusive: the same token can't be retired twice
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.source.to_account_info(),
+                    authority: ctx.accounts.retiree.to_account_info(),
+                },
+            ),
+            params.amount,
+        )?;
+
+        let retiree_account = &mut ctx.accounts.retiree_account;
+        let index = retiree_account.retirement_count;
+        retiree_account.retiree = ctx.accounts.retiree.key();
+        retiree_account.retirement_count = index + 1;
+        retiree_account.total_retired = retiree_account
+            .total_retired
+            .checked_add(params.amount)
+            .ok_or(CustomError::InvalidAmount)?;
+        retiree_account.bump = ctx.bumps.retiree_account;
+
+        let retirement = &mut ctx.accounts.retirement;
+        retirement.retiree = ctx.accounts.retiree.key();
+        retirement.index = index;
+        retirement.amount = params.amount;
+        retirement.liters = liters;
+        retirement.beneficiary_name = params.beneficiary_name;
+        retirement.beneficiary_hash = params.beneficiary_hash;
+        retirement.vintage_start = params.vintage_start;
+        retirement.vintage_end = params.vintage_end;
+        retirement.sources = params.sources;
+        retirement.batches = params.batches;
+        retirement.reason = params.reason;
+        retirement.retired_at = now;
+        retirement.bump = ctx.bumps.retirement;
+
+        emit!(JalRetired {
+            retirement: retirement.key(),
+            retiree: retirement.retiree,
+            index,
+            amount: retirement.amount,
+            beneficiary_hash: retirement.beneficiary_hash,
+            vintage_start: retirement.vintage_start,
+            vintage_end: retirement.vintage_end,
+            total_retired: ctx.accounts.retiree_account.total_retired,
+        });
+        Ok(())
+    }
+
+    // === RETIREE MINTS A NON-TRANSFERABLE PROOF OF A RETIREMENT ===
+    pub fn issue_retirement_certificate(ctx: Context<IssueRetirementCertificate>) -> Result<()> {
+        let seeds = &[b"mint_auth".as_ref(), &[ctx.accounts.config.mint_auth_bump]];
+        let signer = &[&seeds[..]];
+
+        // One token, held by the retiree...
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.certificate_mint.to_account_info(),
+                    to: ctx.accounts.certificate_token.to_account_info(),
+                    authority: ctx.accounts.mint_auth.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        // ...frozen in place so it can never be moved...
+        token::freeze_account(CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.user_token.to_account_info(),
+            FreezeAccount {
+                account: ctx.accounts.certificate_token.to_account_info(),
+                mint: ctx.accounts.certificate_mint.to_account_info(),
                 authority: ctx.accounts.mint_auth.to_account_info(),
             },
             signer,
-        ),
-        amount,
-    )?;
-    Ok(())
-}
+        ))?;
 
-// === ACCOUNTS ===
-#[derive(Accounts)]
-#[instruction(meter_id: String)]
-pub struct RecordReading<'info> {
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + 4 + 50 + 8 + 8 + 1 + 32 + 8,
-    )]
-    pub reading: Account<'info, WaterReading>,
+        // ...and with both authorities dropped, supply and freeze state are final
+        for authority_type in [AuthorityType::MintTokens, AuthorityType::FreezeAccount] {
+            token::set_authority(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: ctx.accounts.mint_auth.to_account_info(),
+                        account_or_mint: ctx.accounts.certificate_mint.to_account_info(),
+                    },
+                    signer,
+                ),
+                authority_type,
+                None,
+            )?;
+        }
 
-    #[account(seeds = [b"config"], bump)]
-    pub config: Account<'info, MintConfig>,
+        // The volume is what the drawn batches were issued for, not today's rate
+        let retirement = &ctx.accounts.retirement;
+        require!(!retirement.batches.is_empty(), CustomError::RetirementUnbacked);
+        let certificate = &mut ctx.accounts.certificate;
+        certificate.retirement = retirement.key();
+        certificate.mint = ctx.accounts.certificate_mint.key();
+        certificate.owner = retirement.retiree;
+        certificate.amount = retirement.amount;
+        certificate.amount_m3 = retirement.liters / LITERS_PER_M3;
+        certificate.vintage_start = retirement.vintage_start;
+        certificate.vintage_end = retirement.vintage_end;
+        certificate.issued_at = Clock::get()?.unix_timestamp;
+        certificate.bump = ctx.bumps.certificate;
 
-    #[account(seeds = [b"mint_auth"], bump)]
-    pub mint_auth: Signer<'info>,
+        emit!(CertificateIssued {
+            certificate: certificate.key(),
+            retirement: certificate.retirement,
+            mint: certificate.mint,
+            owner: certificate.owner,
+            amount_m3: certificate.amount_m3,
+        });
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    // === SUPER-ADMIN GRANTS A ROLE ===
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
 
-    #[account(mut)]
-    pub user_token: Account<'info, TokenAccount>,
+        let assignment = &mut ctx.accounts.role_assignment;
+        assignment.role = role;
+        assignment.holder = holder;
+        assignment.granted_by = ctx.accounts.admin.key();
+        assignment.granted_at = Clock::get()?.unix_timestamp;
+        assignment.bump = ctx.bumps.role_assignment;
 
-    #[account(
-        constraint = previous_reading.is_none() || 
-        previous_reading.as_ref().unwrap().meter_id == meter_id
-    )]
-    pub previous_reading: Option<Account<'info, WaterReading>>,
+        emit!(RoleGranted {
+            role,
+            holder,
+            by: assignment.granted_by,
+            timestamp: assignment.granted_at,
+        });
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub admin: Signer<'info>,  // ← ONLY ADMIN
+    // === SUPER-ADMIN REVOKES A ROLE ===
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+        let assignment = &ctx.accounts.role_assignment;
+        emit!(RoleRevoked {
+            role: assignment.role,
+            holder: assignment.holder,
+            by: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct UpdateConfig<'info> {
-    #[account(mut, seeds = [b"config"], bump)]
-    pub config: Account<'info, MintConfig>,
+    // === SUPER-ADMIN PROPOSES A SUCCESSOR (STEP 1 OF 2) ===
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(ctx.accounts.admin.key() == config.admin, CustomError::Unauthorized);
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
-}
+        config.pending_admin = new_admin;
 
-#[account]
-pub struct WaterReading {
-    pub meter_id: String,
-    pub reading: u64,
-    pub timestamp: i64,
-    pub meter_type: MeterType,
-    pub authority: Pubkey,
-}
+        emit!(AdminTransferProposed {
+            admin: config.admin,
+            proposed: new_admin,
+        });
+        Ok(())
+    }
 
-#[account]
-pub struct MintConfig {
-    pub residential_threshold: u64,
-    pub municipal_multiplier: u8,
-    pub jal_per_liter: u64,
-    pub admin: Pubkey,
-}
+    // === PROPOSED ADMIN ACCEPTS (STEP 2 OF 2) ===
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(
+            config.pending_admin != Pubkey::default()
+                && ctx.accounts.new_admin.key() == config.pending_admin,
+            CustomError::Unauthorized
+        );
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum MeterType { Residential, Industrial, Municipal }
+        let previous = config.admin;
+        config.admin = config.pending_admin;
+        config.pending_admin = Pubkey::default();
 
-#[error_code]
-pub enum CustomError {
-    #[msg("Invalid meter ID")]
-    InvalidMeterId,
-    #[msg("Reading must be > 0")]
-    InvalidReading,
-    #[msg("Only admin can call this")]
-    Unauthorized,
-}
\ No newline at end of file
+        emit!(AdminTransferred {
+            previous,
+            admin: config.admin,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR REGISTERS A PHYSICAL METER ===
+    pub fn register_meter(
+        ctx: Context<RegisterMeter>,
+        meter_id: String,
+        meter_type: MeterType,
+        building: Pubkey,
+        region: u16,
+        install_date: i64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            !meter_id.is_empty() && meter_id.len() <= MAX_METER_ID_LEN,
+            CustomError::InvalidMeterId
+        );
+        require!(install_date <= clock.unix_timestamp, CustomError::InvalidInstallDate);
+
+        let meter = &mut ctx.accounts.meter;
+        meter.meter_id = meter_id;
+        meter.meter_type = meter_type;
+        meter.owner = ctx.accounts.beneficiary.owner;
+        meter.beneficiary = ctx.accounts.beneficiary.key();
+        meter.building = building;
+        meter.region = region;
+        meter.install_date = install_date;
+        meter.status = MeterStatus::Active;
+        meter.registered_at = clock.unix_timestamp;
+        meter.baseline_updated_at = clock.unix_timestamp;
+        meter.bump = ctx.bumps.meter;
+
+        emit!(MeterRegistered {
+            meter: meter.key(),
+            meter_id: meter.meter_id.clone(),
+            meter_type: meter.meter_type,
+            owner: meter.owner,
+            beneficiary: meter.beneficiary,
+            building: meter.building,
+            region: meter.region,
+            install_date: meter.install_date,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR RETIRES A METER, NO MORE READINGS ACCEPTED ===
+    pub fn deactivate_meter(ctx: Context<DeactivateMeter>) -> Result<()> {
+        let meter = &mut ctx.accounts.meter;
+        require!(meter.status == MeterStatus::Active, CustomError::MeterInactive);
+        meter.status = MeterStatus::Inactive;
+
+        emit!(MeterDeactivated {
+            meter: meter.key(),
+            by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR SETS HOW MANY DIGITS THE METER'S REGISTER HAS (0 = NEVER ROLLS OVER) ===
+    pub fn set_meter_register(ctx: Context<SetMeterRegister>, register_digits: u8) -> Result<()> {
+        require!(register_digits <= MAX_REGISTER_DIGITS, CustomError::InvalidRegister);
+        let meter = &mut ctx.accounts.meter;
+        let old_register_digits = meter.register_digits;
+        meter.register_digits = register_digits;
+
+        emit!(MeterRegisterSet {
+            meter: meter.key(),
+            old_register_digits,
+            register_digits,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR SWAPS THE PHYSICAL METER, CARRYING HISTORY TO A NEW METER PDA ===
+    // The old device's final value must already be on chain as an attested
+    // record_reading; `final_register` only confirms it. `start_register` is the
+    // new device's first value.
+    pub fn replace_meter(
+        ctx: Context<ReplaceMeter>,
+        new_meter_id: String,
+        final_register: u64,
+        start_register: u64,
+        unit: VolumeUnit,
+        register_digits: u8,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !new_meter_id.is_empty() && new_meter_id.len() <= MAX_METER_ID_LEN,
+            CustomError::InvalidMeterId
+        );
+        require!(register_digits <= MAX_REGISTER_DIGITS, CustomError::InvalidRegister);
+
+        let old = &ctx.accounts.old_meter;
+        require!(old.status == MeterStatus::Active, CustomError::MeterInactive);
+
+        // Volume is never taken on the registrar's word: it went through quorum,
+        // device signature and plausibility checks when it was recorded
+        require!(old.reading_count > 0, CustomError::FinalReadingNotRecorded);
+        require!(
+            unit.to_liters(final_register)? == old.last_register,
+            CustomError::FinalReadingNotRecorded
+        );
+        let final_reading = old.last_reading;
+        let start = unit.to_liters(start_register)?;
+        let register_offset = i64::try_from(final_reading as i128 - start as i128)
+            .map_err(|_| CustomError::Overflow)?;
+
+        let meter = &mut ctx.accounts.new_meter;
+        meter.meter_id = new_meter_id;
+        meter.meter_type = old.meter_type;
+        meter.owner = old.owner;
+        meter.beneficiary = old.beneficiary;
+        meter.building = old.building;
+        meter.region = old.region;
+        meter.install_date = now;
+        meter.status = MeterStatus::Active;
+        meter.registered_at = now;
+        // Same sequence space continues, so this isn't treated as a first reading
+        meter.reading_count = old.reading_count;
+        meter.last_reading = final_reading;
+        meter.last_reading_at = old.last_reading_at;
+        meter.baseline_lpd = old.baseline_lpd;
+        meter.baseline_updated_at = old.baseline_updated_at;
+        meter.period_start_reading = old.period_start_reading;
+        meter.period_start_at = old.period_start_at;
+        meter.frozen = old.frozen;
+        meter.issuance_remainder = old.issuance_remainder;
+        meter.flow_mean_lpd = old.flow_mean_lpd;
+        meter.flow_var = old.flow_var;
+        meter.flow_samples = old.flow_samples;
+        meter.register_digits = register_digits;
+        meter.register_offset = register_offset;
+        meter.last_register = start;
+        meter.replaces = old.key();
+        meter.bump = ctx.bumps.new_meter;
+
+        let new_key = meter.key();
+        let old = &mut ctx.accounts.old_meter;
+        old.status = MeterStatus::Replaced;
+        old.replaced_by = new_key;
+
+        emit!(MeterReplaced {
+            old_meter: old.key(),
+            new_meter: new_key,
+            final_reading,
+            start_register,
+            unit,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR POINTS A FACILITY, DISTRICT OR TALLY AT A REPLACEMENT METER ===
+    // Readings continue across the swap, so stored snapshots stay valid as they are
+    pub fn relink_meter(ctx: Context<RelinkMeter>) -> Result<()> {
+        let old = ctx.accounts.old_meter.key();
+        let new = ctx.accounts.new_meter.key();
+        require!(
+            ctx.accounts.old_meter.status == MeterStatus::Replaced,
+            CustomError::MeterNotReplaced
+        );
+
+        let mut relinked = Vec::with_capacity(3);
+        if let Some(facility) = ctx.accounts.facility.as_mut() {
+            if facility.inflow_meter == old {
+                facility.inflow_meter = new;
+            } else if facility.outflow_meter == old {
+                facility.outflow_meter = new;
+            } else {
+                return err!(CustomError::MeterNotLinked);
+            }
+            relinked.push(facility.key());
+        }
+        if let Some(district) = ctx.accounts.district.as_mut() {
+            if district.inflow_meter == old {
+                district.inflow_meter = new;
+            } else if let Some(customer) = district.customers.iter_mut().find(|c| c.meter == old) {
+                customer.meter = new;
+            } else {
+                return err!(CustomError::MeterNotLinked);
+            }
+            relinked.push(district.key());
+        }
+        if let Some(tally) = ctx.accounts.tally.as_mut() {
+            require_keys_eq!(tally.meter, old, CustomError::MeterNotLinked);
+            // The tally keeps its PDA (seeded by the old meter) and its streak
+            tally.meter = new;
+            ctx.accounts.old_meter.tally = Pubkey::default();
+            ctx.accounts.new_meter.tally = tally.key();
+            relinked.push(tally.key());
+        }
+        require!(!relinked.is_empty(), CustomError::MeterNotLinked);
+
+        for record in relinked {
+            emit!(MeterRelinked {
+                record,
+                old_meter: old,
+                new_meter: new,
+            });
+        }
+        Ok(())
+    }
+
+    // === REGISTRAR MOVES A METER TO A NEW OWNER (E.G. TENANT CHANGE) ===
+    pub fn transfer_meter(ctx: Context<TransferMeter>) -> Result<()> {
+        let meter = &mut ctx.accounts.meter;
+        require!(meter.status == MeterStatus::Active, CustomError::MeterInactive);
+        let previous_owner = meter.owner;
+        meter.owner = ctx.accounts.new_beneficiary.owner;
+        meter.beneficiary = ctx.accounts.new_beneficiary.key();
+
+        emit!(MeterTransferred {
+            meter: meter.key(),
+            previous_owner,
+            owner: meter.owner,
+            beneficiary: meter.beneficiary,
+        });
+        Ok(())
+    }
+
+    // === ONLY REPORTERS CAN RECORD ===
+    pub fn record_reading(
+        ctx: Context<RecordReading>,
+        payload: MeterPayload,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let config = &ctx.accounts.config;
+        let meter = &ctx.accounts.meter;
+        // Everything downstream works in liters, on a counter that survives rollovers and swaps
+        let register = payload.unit.to_liters(payload.reading)?;
+        let (reading, register_offset) = meter.continuous_reading(register, payload.unit)?;
+
+        // === VALIDATE ===
+        require!(payload.meter_id == meter.meter_id, CustomError::InvalidMeterId);
+        require!(reading > 0, CustomError::InvalidReading);
+        require!(meter.status == MeterStatus::Active, CustomError::MeterInactive);
+        require!(!meter.frozen, CustomError::MeterFrozen);
+        config.check_not_paused(meter.meter_type)?;
+        require!(reading >= meter.last_reading, CustomError::ReadingBelowPrevious);
+
+        // === REPLAY PROTECTION ===
+        require!(payload.nonce > meter.device_nonce, CustomError::StaleNonce);
+        require!(
+            payload.device_timestamp.abs_diff(clock.unix_timestamp)
+                <= config.max_clock_drift_secs as u64,
+            CustomError::DeviceClockDrift
+        );
+
+        // === TYPE AND BASELINE COME FROM THE METER, NOT THE CALLER ===
+        let meter_type = meter.meter_type;
+        let sequence = meter.reading_count;
+        let previous = meter.last_reading;
+
+        // === DEVICE SIGNATURE, ONCE A DEVICE KEY IS REGISTERED (OR ALWAYS, IF REQUIRED) ===
+        let device_signed = meter.device_key != Pubkey::default();
+        require!(
+            device_signed || !config.require_device_signature,
+            CustomError::DeviceSignatureMissing
+        );
+        if device_signed {
+            let signers = ed25519_signers(&ctx.accounts.instructions, &payload.signing_message()?)?;
+            require!(signers.contains(&meter.device_key), CustomError::DeviceSignatureMissing);
+        }
+
+        // === ORACLE QUORUM FOR THIS METER TYPE ===
+        let quorum = config.oracle_quorum[meter_type as usize];
+        if quorum > 0 {
+            let message = reading_attestation_message(&meter.key(), sequence, reading);
+            let signers = ed25519_signers(&ctx.accounts.instructions, &message)?;
+            let attested = count_oracle_attestations(ctx.remaining_accounts, &signers, meter_type)?;
+            require!(attested >= quorum as usize, CustomError::OracleQuorumNotMet);
+        }
+
+        // === PLAUSIBILITY: REJECT OR QUARANTINE OUTLIERS BEFORE ANYTHING ACCRUES ===
+        let now = clock.unix_timestamp;
+        let rules = &ctx.accounts.plausibility;
+        let anomaly = rules.check(meter, reading, now);
+        if anomaly.is_some() {
+            require!(rules.action == AnomalyAction::Quarantine, CustomError::ImplausibleReading);
+        }
+
+        // === AUTO MINT LOGIC ===
+        let decimals = ctx.accounts.mint.decimals;
+        let mut remainder = meter.issuance_remainder;
+        let mut period_closed = sequence == 0;
+        let mint_amount = if anomaly.is_some() {
+            None // Quarantined: consumption still counts, savings don't
+        } else {
+            match meter_type {
+                MeterType::Residential => {
+                    // Savings = baseline consumption for the period minus what was actually used
+                    match meter.period_savings(&ctx.accounts.baseline_config, reading, now) {
+                        Some(saved) => {
+                            period_closed = true;
+                            if saved >= config.residential_threshold {
+                                let amount = issuance_amount(
+                                    saved,
+                                    config.jal_per_m3_bps,
+                                    decimals,
+                                    &mut remainder,
+                                )?;
+                                Some((saved, amount))
+                            } else {
+                                None
+                            }
+                        }
+                        None => None, // Period still open
+                    }
+                }
+                MeterType::Municipal => {
+                    None // Minted per district via close_district_period
+                }
+                MeterType::Industrial => {
+                    None // Minted per facility via claim_reclamation
+                }
+            }
+        };
+
+        // === STORE ===
+        let reading_acc = &mut ctx.accounts.reading;
+        reading_acc.meter = ctx.accounts.meter.key();
+        reading_acc.sequence = sequence;
+        reading_acc.reading = reading;
+        reading_acc.raw_reading = payload.reading;
+        reading_acc.unit = payload.unit;
+        reading_acc.previous_reading = previous;
+        reading_acc.timestamp = clock.unix_timestamp;
+        reading_acc.meter_type = meter_type;
+        reading_acc.authority = ctx.accounts.authority.key();
+        reading_acc.device_timestamp = payload.device_timestamp;
+        reading_acc.nonce = payload.nonce;
+        reading_acc.device_signed = device_signed;
+        if anomaly.is_some() {
+            reading_acc.status = ReadingStatus::Quarantined;
+        }
+        reading_acc.bump = ctx.bumps.reading;
+
+        // === ADVANCE THE METER SO THIS DELTA CAN NEVER BE REUSED ===
+        let meter = &mut ctx.accounts.meter;
+        if period_closed {
+            meter.close_period(&ctx.accounts.baseline_config, reading, now);
+        }
+        // Outliers never feed the rolling stats they're judged against
+        if anomaly.is_none() {
+            meter.observe_flow(&ctx.accounts.plausibility, reading, now);
+        }
+        meter.reading_count = sequence + 1;
+        meter.last_reading = reading;
+        meter.last_register = register;
+        meter.register_offset = register_offset;
+        meter.last_reading_at = clock.unix_timestamp;
+        meter.device_nonce = payload.nonce;
+        meter.issuance_remainder = remainder;
+
+        emit!(ReadingRecorded {
+            meter: meter.key(),
+            reading: ctx.accounts.reading.key(),
+            sequence,
+            value: reading,
+            previous,
+            meter_type,
+            device_signed,
+            timestamp: now,
+        });
+
+        if let Some(kind) = anomaly {
+            emit!(ReadingQuarantined {
+                meter: ctx.accounts.meter.key(),
+                reading: ctx.accounts.reading.key(),
+                kind,
+            });
+        }
+
+        // === ACCRUE TO THE METER'S LEDGER; TOKENS ONLY LEAVE VIA claim_rewards ===
+        let ledger = &mut ctx.accounts.reward_ledger;
+        ledger.meter = ctx.accounts.meter.key();
+        ledger.owner = ctx.accounts.meter.owner;
+        ledger.bump = ctx.bumps.reward_ledger;
+
+        if let Some((liters_saved, amount)) = mint_amount {
+            if amount > 0 {
+                // Supply is reserved now so a later claim can't hit the cap
+                ctx.accounts
+                    .tokenomics
+                    .charge(AllocationBucket::CommunityRewards, amount)?;
+                ledger.pending += amount;
+
+                let reading_acc = &mut ctx.accounts.reading;
+                reading_acc.reward_liters = liters_saved;
+                reading_acc.reward = amount;
+                reading_acc.reward_owner = ledger.owner;
+                reading_acc.reward_status = RewardStatus::Pending;
+
+                emit!(RewardAccrued {
+                    meter: ctx.accounts.meter.key(),
+                    reading: ctx.accounts.reading.key(),
+                    liters_saved,
+                    amount,
+                    meter_type,
+                    settles_at: now + config.settlement_window_secs,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // === ANYONE CRANKS: A READING'S REWARD BECOMES CLAIMABLE AFTER THE WINDOW ===
+    pub fn settle_reward(ctx: Context<SettleReward>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let reading = &mut ctx.accounts.reading;
+        ctx.accounts.config.check_not_paused(reading.meter_type)?;
+        require!(reading.reward_status == RewardStatus::Pending, CustomError::RewardNotPending);
+        require!(reading.status == ReadingStatus::Valid, CustomError::ReadingNotValid);
+        require!(
+            now >= reading.timestamp + ctx.accounts.config.settlement_window_secs,
+            CustomError::SettlementWindowOpen
+        );
+
+        reading.reward_status = RewardStatus::Settled;
+        let ledger = &mut ctx.accounts.reward_ledger;
+        reading.claim_epoch = ledger.claim_epoch;
+        ledger.pending -= reading.reward;
+        ledger.claimable += reading.reward;
+        ledger.claimable_liters += reading.reward_liters;
+
+        // === PROVENANCE: BOOK AGAINST THE VINTAGE BATCH ONLY ONCE SETTLED ===
+        // Retirements then never draw on rewards still pending or under dispute
+        ctx.accounts.batch.book(
+            reading.meter_type,
+            ctx.accounts.meter.region,
+            vintage_year(reading.device_timestamp),
+            reading.reward_liters,
+            reading.reward,
+            ctx.bumps.batch,
+        );
+
+        reading.tallied_period = tally_settled_reward(
+            &ctx.accounts.meter,
+            ctx.accounts.leaderboard.as_deref_mut(),
+            ctx.accounts.tally.as_deref_mut(),
+            reading.reward_liters,
+        )?;
+
+        emit!(RewardSettled {
+            meter: ledger.meter,
+            reading: reading.key(),
+            amount: reading.reward,
+            claimable: ledger.claimable,
+        });
+        Ok(())
+    }
+
+    // === LEDGER OWNER MINTS THEIR SETTLED REWARDS INTO THEIR OWN ATA ===
+    // Rewards stay with whoever owned the meter when they accrued, across transfers
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let meter_type = ctx.accounts.meter.meter_type;
+        ctx.accounts.config.check_not_paused(meter_type)?;
+        let amount = ctx.accounts.reward_ledger.claimable;
+        require!(amount > 0, CustomError::NothingToClaim);
+
+        // Already charged to CommunityRewards at accrual
+        mint_jal(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.mint_auth,
+            ctx.accounts.config.mint_auth_bump,
+            ctx.accounts.destination.to_account_info(),
+            amount,
+        )?;
+
+        let ledger = &mut ctx.accounts.reward_ledger;
+        let liters_saved = ledger.claimable_liters;
+        ledger.claimable = 0;
+        ledger.claimable_liters = 0;
+        ledger.total_claimed += amount;
+        // Everything settled so far is now minted and out of clawback's reach
+        ledger.claim_epoch += 1;
+
+        emit!(JalMinted {
+            meter: ledger.meter,
+            beneficiary: ctx.accounts.destination.key(),
+            liters_saved,
+            amount,
+            meter_type,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // === SUPER-ADMIN VOIDS A READING'S REWARD ANY TIME BEFORE IT'S CLAIMED ===
+    pub fn clawback_reward(ctx: Context<ClawbackReward>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
+        let reading = &ctx.accounts.reading;
+        // Disputed readings are settled through resolve_dispute
+        require!(reading.status == ReadingStatus::Valid, CustomError::ReadingNotValid);
+        let (liters, amount) = (reading.reward_liters, reading.reward);
+
+        let ledger = &mut ctx.accounts.reward_ledger;
+        match reading.reward_status {
+            // Not booked against its batch until it settles
+            RewardStatus::Pending => ledger.pending -= amount,
+            // Settled rewards are pooled; only those settled since the last claim are unminted
+            RewardStatus::Settled => {
+                require!(
+                    reading.claim_epoch == ledger.claim_epoch,
+                    CustomError::RewardAlreadyClaimed
+                );
+                ledger.claimable -= amount;
+                ledger.claimable_liters = ledger.claimable_liters.saturating_sub(liters);
+                ctx.accounts
+                    .batch
+                    .as_mut()
+                    .ok_or(CustomError::InvalidBatch)?
+                    .unbook(liters, amount)?;
+                if let Some(period) = reading.tallied_period {
+                    untally_reward(
+                        &ctx.accounts.meter,
+                        ctx.accounts.leaderboard.as_deref_mut(),
+                        ctx.accounts.tally.as_deref_mut(),
+                        period,
+                        liters,
+                    )?;
+                }
+            }
+            _ => return err!(CustomError::RewardNotPending),
+        }
+        ledger.total_clawed_back += amount;
+
+        ctx.accounts
+            .tokenomics
+            .release(AllocationBucket::CommunityRewards, amount);
+
+        let reading = &mut ctx.accounts.reading;
+        reading.reward_status = RewardStatus::ClawedBack;
+        reading.status = ReadingStatus::Invalid;
+
+        emit!(RewardClawedBack {
+            meter: ledger.meter,
+            reading: ctx.accounts.reading.key(),
+            amount,
+            by: ctx.accounts.admin.key(),
+        });
+        Ok(())
+    }
+
+    // === AUDITOR FLAGS A READING INSIDE ITS CHALLENGE PERIOD, FREEZING ITS ACCRUAL ===
+    pub fn dispute_reading(ctx: Context<DisputeReading>, reason_code: u16) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let reading = &mut ctx.accounts.reading;
+        require!(reading.status == ReadingStatus::Valid, CustomError::ReadingNotValid);
+        require!(reading.reward_status == RewardStatus::Pending, CustomError::RewardNotPending);
+        require!(
+            now < reading.timestamp + ctx.accounts.config.settlement_window_secs,
+            CustomError::ChallengePeriodOver
+        );
+
+        reading.status = ReadingStatus::Disputed;
+        reading.dispute_reason = reason_code;
+        reading.disputed_by = ctx.accounts.authority.key();
+        reading.disputed_at = now;
+
+        let ledger = &mut ctx.accounts.reward_ledger;
+        ledger.pending -= reading.reward;
+        ledger.disputed += reading.reward;
+
+        emit!(ReadingDisputed {
+            meter: reading.meter,
+            reading: reading.key(),
+            reason_code,
+            amount: reading.reward,
+            by: reading.disputed_by,
+        });
+        Ok(())
+    }
+
+    // === AUDITOR RESOLVES A DISPUTE: RELEASE THE REWARD OR VOID THE READING ===
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, valid: bool) -> Result<()> {
+        let reading = &ctx.accounts.reading;
+        require!(reading.status == ReadingStatus::Disputed, CustomError::ReadingNotDisputed);
+        let (liters, amount) = (reading.reward_liters, reading.reward);
+
+        if valid {
+            // The audit stands in for the settlement window
+            let ledger = &mut ctx.accounts.reward_ledger;
+            ledger.disputed -= amount;
+            ledger.claimable += amount;
+            ledger.claimable_liters += liters;
+            let reading = &mut ctx.accounts.reading;
+            reading.status = ReadingStatus::Valid;
+            reading.reward_status = RewardStatus::Settled;
+            reading.claim_epoch = ledger.claim_epoch;
+            ctx.accounts.batch.book(
+                reading.meter_type,
+                ctx.accounts.meter.region,
+                vintage_year(reading.device_timestamp),
+                liters,
+                amount,
+                ctx.bumps.batch,
+            );
+            reading.tallied_period = tally_settled_reward(
+                &ctx.accounts.meter,
+                ctx.accounts.leaderboard.as_deref_mut(),
+                ctx.accounts.tally.as_deref_mut(),
+                liters,
+            )?;
+        } else {
+            // Never settled, so never booked against its batch
+            ctx.accounts
+                .tokenomics
+                .release(AllocationBucket::CommunityRewards, amount);
+
+            let ledger = &mut ctx.accounts.reward_ledger;
+            ledger.disputed -= amount;
+            ledger.total_clawed_back += amount;
+            let reading = &mut ctx.accounts.reading;
+            reading.status = ReadingStatus::Invalid;
+            reading.reward_status = RewardStatus::ClawedBack;
+        }
+
+        emit!(DisputeResolved {
+            meter: ctx.accounts.reading.meter,
+            reading: ctx.accounts.reading.key(),
+            valid,
+            amount,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === AUDITOR RELEASES OR VOIDS A QUARANTINED READING ===
+    // Nothing accrued on it either way: its consumption already counts toward the
+    // open period, whose savings are settled by the meter's next clean reading
+    pub fn resolve_quarantine(ctx: Context<ResolveQuarantine>, valid: bool) -> Result<()> {
+        let reading = &mut ctx.accounts.reading;
+        require!(
+            reading.status == ReadingStatus::Quarantined,
+            CustomError::ReadingNotQuarantined
+        );
+        reading.status = if valid { ReadingStatus::Valid } else { ReadingStatus::Invalid };
+
+        emit!(QuarantineResolved {
+            meter: reading.meter,
+            reading: reading.key(),
+            valid,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR PAIRS A PLANT'S RAW-INFLOW AND TREATED-OUTFLOW METERS ===
+    pub fn register_facility(
+        ctx: Context<RegisterFacility>,
+        facility_id: String,
+        period_secs: i64,
+        max_liters_per_period: u64,
+    ) -> Result<()> {
+        require!(
+            !facility_id.is_empty() && facility_id.len() <= MAX_METER_ID_LEN,
+            CustomError::InvalidFacility
+        );
+        require!(period_secs > 0, CustomError::InvalidFacility);
+
+        let inflow = &ctx.accounts.inflow_meter;
+        let outflow = &ctx.accounts.outflow_meter;
+        require_keys_neq!(inflow.key(), outflow.key(), CustomError::InvalidFacility);
+        for meter in [inflow, outflow] {
+            require!(meter.meter_type == MeterType::Industrial, CustomError::InvalidFacility);
+            require!(meter.status == MeterStatus::Active, CustomError::MeterInactive);
+        }
+
+        let facility = &mut ctx.accounts.facility;
+        facility.facility_id = facility_id;
+        facility.operator = ctx.accounts.beneficiary.owner;
+        facility.beneficiary = ctx.accounts.beneficiary.key();
+        facility.inflow_meter = inflow.key();
+        facility.outflow_meter = outflow.key();
+        facility.region = outflow.region;
+        // Only volume treated after registration counts
+        facility.last_inflow_reading = inflow.last_reading;
+        facility.last_outflow_reading = outflow.last_reading;
+        facility.period_secs = period_secs;
+        facility.max_liters_per_period = max_liters_per_period;
+        facility.open_period = ReclamationPeriod {
+            period: facility.period_index(Clock::get()?.unix_timestamp),
+            ..Default::default()
+        };
+        facility.bump = ctx.bumps.facility;
+
+        emit!(FacilityRegistered {
+            facility: facility.key(),
+            facility_id: facility.facility_id.clone(),
+            inflow_meter: facility.inflow_meter,
+            outflow_meter: facility.outflow_meter,
+            beneficiary: facility.beneficiary,
+            max_liters_per_period,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER SETS A REGION'S TREATED-EFFLUENT THRESHOLDS ===
+    pub fn set_quality_standard(
+        ctx: Context<SetQualityStandard>,
+        region: u16,
+        params: QualityParams,
+    ) -> Result<()> {
+        let standard = &mut ctx.accounts.quality_standard;
+        standard.apply(params)?;
+        standard.region = region;
+        standard.bump = ctx.bumps.quality_standard;
+
+        emit!(QualityStandardSet {
+            region,
+            params,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === LAB/ORACLE FILES A FACILITY'S MEASUREMENTS FOR A PERIOD ===
+    // Re-filing the same period overwrites the report (lab corrections)
+    pub fn submit_quality_report(
+        ctx: Context<SubmitQualityReport>,
+        period: u64,
+        measurements: QualityMeasurements,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            period <= ctx.accounts.facility.period_index(now),
+            CustomError::InvalidQualityReport
+        );
+
+        let passed = ctx.accounts.quality_standard.passes(&measurements);
+        let report = &mut ctx.accounts.quality_report;
+        report.facility = ctx.accounts.facility.key();
+        report.period = period;
+        report.measurements = measurements;
+        report.passed = passed;
+        report.reported_by = ctx.accounts.authority.key();
+        report.reported_at = now;
+        report.bump = ctx.bumps.quality_report;
+
+        emit!(QualityReported {
+            facility: report.facility,
+            period,
+            measurements,
+            passed,
+            by: report.reported_by,
+        });
+        Ok(())
+    }
+
+    // === ANYONE CRANKS: ATTRIBUTE TREATED VOLUME, MINT A PERIOD ONCE ITS REPORT PASSED ===
+    // `period` is the period being claimed; without a report the call only attributes volume
+    pub fn claim_reclamation(ctx: Context<ClaimReclamation>, period: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let inflow = &ctx.accounts.inflow_meter;
+        let outflow = &ctx.accounts.outflow_meter;
+        config.check_not_paused(MeterType::Industrial)?;
+        require!(!inflow.frozen && !outflow.frozen, CustomError::MeterFrozen);
+
+        // Can't have recycled more than came in
+        let facility = &mut ctx.accounts.facility;
+        let inflow_delta = inflow.last_reading.saturating_sub(facility.last_inflow_reading);
+        let outflow_delta = outflow.last_reading.saturating_sub(facility.last_outflow_reading);
+        let recycled = inflow_delta.min(outflow_delta);
+
+        // Volume belongs to the period its latest outflow reading was taken in, not the
+        // period the crank happens to run in
+        let reading_period = facility.period_index(outflow.last_reading_at);
+        facility.last_inflow_reading = inflow.last_reading;
+        facility.last_outflow_reading = outflow.last_reading;
+        if let Some(stale) = facility.attribute(reading_period, recycled) {
+            emit!(ReclamationForfeited {
+                facility: facility.key(),
+                period: stale.period,
+                recycled_liters: stale.recycled_liters,
+            });
+        }
+
+        // Volume only counts in a period whose effluent passed the region's standard
+        let Some(report) = ctx.accounts.quality_report.as_ref() else {
+            return Ok(());
+        };
+        require_keys_eq!(report.facility, facility.key(), CustomError::QualityNotAttested);
+        require!(report.period == period, CustomError::QualityNotAttested);
+
+        let facility_key = facility.key();
+        let max_liters = facility.max_liters_per_period;
+        let claimed = if facility.open_period.period == period {
+            &mut facility.open_period
+        } else {
+            match facility.closed_period.as_mut() {
+                Some(closed) if closed.period == period => closed,
+                _ => return err!(CustomError::QualityNotAttested),
+            }
+        };
+        let pending = std::mem::take(&mut claimed.recycled_liters);
+
+        if !report.passed {
+            if facility.closed_period.map(|closed| closed.period) == Some(period) {
+                facility.closed_period = None;
+            }
+            emit!(ReclamationForfeited {
+                facility: facility_key,
+                period,
+                recycled_liters: pending,
+            });
+            return Ok(());
+        }
+
+        // The pending volume is consumed; volume over the cap is forfeited, not deferred
+        let headroom = max_liters.saturating_sub(claimed.credited_liters);
+        let credited = pending.min(headroom);
+        claimed.credited_liters += credited;
+        if facility.closed_period.map(|closed| closed.period) == Some(period) {
+            facility.closed_period = None;
+        }
+
+        let mut remainder = facility.issuance_remainder;
+        let amount = issuance_amount(
+            credited,
+            config.jal_per_m3_bps,
+            ctx.accounts.mint.decimals,
+            &mut remainder,
+        )?;
+        facility.total_recycled_liters += credited;
+        facility.issuance_remainder = remainder;
+
+        if amount > 0 {
+            ctx.accounts
+                .tokenomics
+                .charge(AllocationBucket::CommunityRewards, amount)?;
+            mint_jal(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                &ctx.accounts.mint_auth,
+                ctx.accounts.config.mint_auth_bump,
+                ctx.accounts.beneficiary.to_account_info(),
+                amount,
+            )?;
+            let vintage = vintage_year(ctx.accounts.facility.period_start(period));
+            ctx.accounts.batch.book(
+                MeterType::Industrial,
+                ctx.accounts.facility.region,
+                vintage,
+                credited,
+                amount,
+                ctx.bumps.batch,
+            );
+        }
+
+        emit!(ReclamationMinted {
+            facility: facility_key,
+            period,
+            recycled_liters: pending,
+            credited_liters: credited,
+            amount,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR SETS UP A DISTRICT METERED AREA AROUND ITS BULK INFLOW METER ===
+    pub fn register_district(
+        ctx: Context<RegisterDistrict>,
+        district_id: String,
+        baseline_loss_bps: u16,
+        period_secs: i64,
+    ) -> Result<()> {
+        require!(
+            !district_id.is_empty() && district_id.len() <= MAX_METER_ID_LEN,
+            CustomError::InvalidDistrict
+        );
+        require!(
+            baseline_loss_bps as u64 <= BPS_DENOMINATOR && period_secs > 0,
+            CustomError::InvalidDistrict
+        );
+
+        let inflow = &ctx.accounts.inflow_meter;
+        require!(inflow.meter_type == MeterType::Municipal, CustomError::InvalidDistrict);
+        require!(inflow.status == MeterStatus::Active, CustomError::MeterInactive);
+
+        let district = &mut ctx.accounts.district;
+        district.district_id = district_id;
+        district.utility = ctx.accounts.beneficiary.owner;
+        district.beneficiary = ctx.accounts.beneficiary.key();
+        district.inflow_meter = inflow.key();
+        district.region = inflow.region;
+        district.baseline_loss_bps = baseline_loss_bps;
+        district.period_secs = period_secs;
+        district.period_start_at = Clock::get()?.unix_timestamp;
+        district.last_inflow_reading = inflow.last_reading;
+        district.bump = ctx.bumps.district;
+
+        emit!(DistrictRegistered {
+            district: district.key(),
+            district_id: district.district_id.clone(),
+            inflow_meter: district.inflow_meter,
+            beneficiary: district.beneficiary,
+            baseline_loss_bps,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR ADDS A DOWNSTREAM CUSTOMER METER TO A DISTRICT ===
+    pub fn add_district_meter(ctx: Context<UpdateDistrictMeters>) -> Result<()> {
+        let meter = &ctx.accounts.meter;
+        let district = &mut ctx.accounts.district;
+        require!(meter.status == MeterStatus::Active, CustomError::MeterInactive);
+        require!(
+            meter.key() != district.inflow_meter
+                && district.customers.len() < MAX_DISTRICT_CUSTOMERS
+                && !district.customers.iter().any(|c| c.meter == meter.key()),
+            CustomError::InvalidDistrict
+        );
+
+        district.customers.push(DistrictCustomer {
+            meter: meter.key(),
+            snapshot: meter.last_reading,
+        });
+
+        emit!(DistrictMeterSet {
+            district: district.key(),
+            meter: meter.key(),
+            added: true,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR DROPS A CUSTOMER METER FROM A DISTRICT ===
+    pub fn remove_district_meter(ctx: Context<UpdateDistrictMeters>) -> Result<()> {
+        let meter = ctx.accounts.meter.key();
+        let district = &mut ctx.accounts.district;
+        let before = district.customers.len();
+        district.customers.retain(|c| c.meter != meter);
+        require!(district.customers.len() < before, CustomError::InvalidDistrict);
+
+        emit!(DistrictMeterSet {
+            district: district.key(),
+            meter,
+            added: false,
+        });
+        Ok(())
+    }
+
+    // === ANYONE CRANKS: MINT FOR NON-REVENUE WATER BELOW THE DISTRICT'S BASELINE ===
+    // remaining_accounts = the district's customer meters, in registration order
+    pub fn close_district_period(ctx: Context<CloseDistrictPeriod>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let inflow = &ctx.accounts.inflow_meter;
+        config.check_not_paused(MeterType::Municipal)?;
+        require!(!inflow.frozen, CustomError::MeterFrozen);
+
+        let district = &ctx.accounts.district;
+        require!(
+            now >= district.period_start_at + district.period_secs,
+            CustomError::DistrictPeriodOpen
+        );
+
+        let supplied = inflow.last_reading.saturating_sub(district.last_inflow_reading);
+        let customer_readings = district_customer_readings(ctx.remaining_accounts, district)?;
+        let metered = district
+            .customers
+            .iter()
+            .zip(&customer_readings)
+            .map(|(c, reading)| reading.saturating_sub(c.snapshot))
+            .sum::<u64>();
+        let DistrictLoss {
+            billed,
+            loss_bps,
+            credited,
+        } = district_loss(
+            supplied,
+            metered,
+            district.baseline_loss_bps,
+            config.municipal_multiplier,
+        );
+        let mut remainder = district.issuance_remainder;
+        let amount = issuance_amount(
+            credited,
+            config.jal_per_m3_bps,
+            ctx.accounts.mint.decimals,
+            &mut remainder,
+        )?;
+
+        let period = district.period_count;
+        let district = &mut ctx.accounts.district;
+        for (customer, reading) in district.customers.iter_mut().zip(customer_readings) {
+            customer.snapshot = reading;
+        }
+        district.last_inflow_reading = inflow.last_reading;
+        district.period_start_at = now;
+        district.period_count += 1;
+        district.total_reduction_liters += credited;
+        district.issuance_remainder = remainder;
+
+        if amount > 0 {
+            ctx.accounts
+                .tokenomics
+                .charge(AllocationBucket::CommunityRewards, amount)?;
+            mint_jal(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                &ctx.accounts.mint_auth,
+                ctx.accounts.config.mint_auth_bump,
+                ctx.accounts.beneficiary.to_account_info(),
+                amount,
+            )?;
+            ctx.accounts.batch.book(
+                MeterType::Municipal,
+                ctx.accounts.district.region,
+                vintage_year(ctx.accounts.inflow_meter.last_reading_at),
+                credited,
+                amount,
+                ctx.bumps.batch,
+            );
+        }
+
+        emit!(DistrictPeriodClosed {
+            district: ctx.accounts.district.key(),
+            period,
+            supplied_liters: supplied,
+            billed_liters: billed,
+            loss_bps,
+            baseline_loss_bps: ctx.accounts.district.baseline_loss_bps,
+            credited_liters: credited,
+            amount,
+        });
+        Ok(())
+    }
+
+    // === REGISTRAR ONBOARDS A RESIDENTS' ASSOCIATION FOR A BUILDING ===
+    // Meters join implicitly through `meter.building`
+    pub fn create_association(
+        ctx: Context<CreateAssociation>,
+        building: Pubkey,
+        name: String,
+        manager: Pubkey,
+        credit_per_jal: u64,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_ASSOCIATION_NAME_LEN,
+            CustomError::InvalidAssociation
+        );
+        require!(credit_per_jal > 0, CustomError::InvalidAssociation);
+
+        let association = &mut ctx.accounts.association;
+        association.building = building;
+        association.name = name;
+        association.manager = manager;
+        association.treasury = ctx.accounts.treasury.key();
+        association.credit_per_jal = credit_per_jal;
+        association.bump = ctx.bumps.association;
+
+        emit!(AssociationCreated {
+            association: association.key(),
+            building,
+            name: association.name.clone(),
+            manager,
+            treasury: association.treasury,
+            credit_per_jal,
+        });
+        Ok(())
+    }
+
+    // === ASSOCIATION MANAGER SETS HOW MUCH FEE CREDIT ONE JAL BUYS ===
+    pub fn set_association_rate(ctx: Context<ManageAssociation>, credit_per_jal: u64) -> Result<()> {
+        require!(credit_per_jal > 0, CustomError::InvalidAssociation);
+        let association = &mut ctx.accounts.association;
+        let old_credit_per_jal = association.credit_per_jal;
+        association.credit_per_jal = credit_per_jal;
+
+        emit!(AssociationRateSet {
+            association: association.key(),
+            old_credit_per_jal,
+            credit_per_jal,
+        });
+        Ok(())
+    }
+
+    // === ASSOCIATION MANAGER MOVES REDEEMED JAL OUT OF THE TREASURY ===
+    pub fn withdraw_association_treasury(
+        ctx: Context<WithdrawAssociationTreasury>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, CustomError::InvalidAmount);
+
+        let association = &ctx.accounts.association;
+        let seeds = &[
+            b"association".as_ref(),
+            association.building.as_ref(),
+            &[association.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.association.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        let association = &mut ctx.accounts.association;
+        association.total_withdrawn += amount;
+
+        emit!(AssociationTreasuryWithdrawn {
+            association: association.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+            by: ctx.accounts.manager.key(),
+        });
+        Ok(())
+    }
+
+    // === RESIDENT HANDS JAL TO THEIR ASSOCIATION FOR A MAINTENANCE-FEE CREDIT ===
+    pub fn redeem_for_fee_discount(ctx: Context<RedeemForFeeDiscount>, amount: u64) -> Result<()> {
+        require!(amount > 0, CustomError::InvalidAmount);
+
+        let credit = ctx.accounts.association.credit_for(amount, ctx.accounts.mint.decimals);
+        require!(credit > 0, CustomError::InvalidAmount);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.resident.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let association = &mut ctx.accounts.association;
+        association.total_redeemed += amount;
+        association.total_credit_issued += credit;
+
+        let fee_credit = &mut ctx.accounts.fee_credit;
+        fee_credit.add(
+            association.key(),
+            ctx.accounts.resident.key(),
+            amount,
+            credit,
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.fee_credit,
+        );
+
+        emit!(FeeDiscountRedeemed {
+            association: association.key(),
+            resident: fee_credit.resident,
+            meter: ctx.accounts.meter.key(),
+            amount,
+            credit,
+            balance: fee_credit.balance,
+        });
+        Ok(())
+    }
+
+    // === ASSOCIATION MANAGER DRAWS DOWN A CREDIT ONCE BILLING HAS APPLIED IT ===
+    pub fn apply_fee_credit(ctx: Context<ApplyFeeCredit>, credit: u64) -> Result<()> {
+        let fee_credit = &mut ctx.accounts.fee_credit;
+        require!(credit > 0 && credit <= fee_credit.balance, CustomError::InvalidAmount);
+        fee_credit.balance -= credit;
+        fee_credit.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(FeeCreditApplied {
+            association: fee_credit.association,
+            resident: fee_credit.resident,
+            credit,
+            balance: fee_credit.balance,
+        });
+        Ok(())
+    }
+
+    // === RESIDENT SIGNS A STANDING FEE MANDATE AND DELEGATES TO IT ===
+    pub fn create_fee_mandate(
+        ctx: Context<CreateFeeMandate>,
+        amount_per_period: u64,
+        period_secs: i64,
+        total_allowance: u64,
+    ) -> Result<()> {
+        require!(
+            amount_per_period > 0 && period_secs > 0 && total_allowance >= amount_per_period,
+            CustomError::InvalidMandate
+        );
+
+        // The mandate PDA, not the association, is the delegate: only the crank can pull
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.source.to_account_info(),
+                    delegate: ctx.accounts.mandate.to_account_info(),
+                    authority: ctx.accounts.resident.to_account_info(),
+                },
+            ),
+            total_allowance,
+        )?;
+
+        let mandate = &mut ctx.accounts.mandate;
+        mandate.association = ctx.accounts.association.key();
+        mandate.resident = ctx.accounts.resident.key();
+        mandate.source = ctx.accounts.source.key();
+        mandate.amount_per_period = amount_per_period;
+        mandate.period_secs = period_secs;
+        mandate.created_at = Clock::get()?.unix_timestamp;
+        mandate.next_due_at = mandate.created_at;
+        mandate.bump = ctx.bumps.mandate;
+
+        emit!(FeeMandateCreated {
+            mandate: mandate.key(),
+            association: mandate.association,
+            resident: mandate.resident,
+            amount_per_period,
+            period_secs,
+            total_allowance,
+        });
+        Ok(())
+    }
+
+    // === ANYONE CRANKS: PULL THIS PERIOD'S FEE UNDER A MANDATE ===
+    pub fn execute_fee_debit(ctx: Context<ExecuteFeeDebit>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let mandate = &ctx.accounts.mandate;
+        require!(!mandate.paused, CustomError::MandatePaused);
+        require!(now >= mandate.next_due_at, CustomError::MandateNotDue);
+
+        let amount = mandate.amount_per_period;
+        let credit = ctx.accounts.association.credit_for(amount, ctx.accounts.mint.decimals);
+        let seeds = &[
+            b"fee_mandate".as_ref(),
+            mandate.association.as_ref(),
+            mandate.resident.as_ref(),
+            &[mandate.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.mandate.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        // One debit per period; missed periods are skipped, never back-billed
+        let mandate = &mut ctx.accounts.mandate;
+        let index = mandate.debit_count;
+        let missed = (now - mandate.next_due_at) / mandate.period_secs;
+        mandate.next_due_at += (missed + 1) * mandate.period_secs;
+        mandate.debit_count += 1;
+        mandate.total_debited += amount;
+
+        let association = &mut ctx.accounts.association;
+        association.total_redeemed += amount;
+        association.total_credit_issued += credit;
+
+        ctx.accounts.fee_credit.add(
+            association.key(),
+            mandate.resident,
+            amount,
+            credit,
+            now,
+            ctx.bumps.fee_credit,
+        );
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.mandate = mandate.key();
+        receipt.association = association.key();
+        receipt.resident = mandate.resident;
+        receipt.index = index;
+        receipt.amount = amount;
+        receipt.credit = credit;
+        receipt.executed_at = now;
+        receipt.bump = ctx.bumps.receipt;
+
+        emit!(FeeDebited {
+            mandate: receipt.mandate,
+            receipt: receipt.key(),
+            resident: receipt.resident,
+            amount,
+            credit,
+            next_due_at: mandate.next_due_at,
+        });
+        Ok(())
+    }
+
+    // === RESIDENT PAUSES OR RESUMES THEIR MANDATE ===
+    pub fn set_fee_mandate_paused(ctx: Context<ManageFeeMandate>, paused: bool) -> Result<()> {
+        let mandate = &mut ctx.accounts.mandate;
+        mandate.paused = paused;
+
+        emit!(FeeMandatePauseSet {
+            mandate: mandate.key(),
+            paused,
+        });
+        Ok(())
+    }
+
+    // === RESIDENT CANCELS THEIR MANDATE, REVOKING THE DELEGATE ===
+    pub fn cancel_fee_mandate(ctx: Context<CancelFeeMandate>) -> Result<()> {
+        token::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.source.to_account_info(),
+                authority: ctx.accounts.resident.to_account_info(),
+            },
+        ))?;
+
+        emit!(FeeMandateCancelled {
+            mandate: ctx.accounts.mandate.key(),
+            resident: ctx.accounts.resident.key(),
+            debit_count: ctx.accounts.mandate.debit_count,
+            total_debited: ctx.accounts.mandate.total_debited,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER SETS (OR RETUNES) A BUILDING'S LEADERBOARD BONUS ===
+    pub fn configure_leaderboard(
+        ctx: Context<ConfigureLeaderboard>,
+        params: LeaderboardParams,
+    ) -> Result<()> {
+        require!(
+            params.top_n > 0
+                && params.top_n <= MAX_LEADERBOARD_TOP_N
+                && params.period_secs > 0
+                && params.streak_bonus_bps as u64 <= BPS_DENOMINATOR,
+            CustomError::InvalidLeaderboard
+        );
+
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        if leaderboard.association == Pubkey::default() {
+            leaderboard.association = ctx.accounts.association.key();
+            leaderboard.period_start_at = Clock::get()?.unix_timestamp;
+            leaderboard.bump = ctx.bumps.leaderboard;
+        }
+        leaderboard.top_n = params.top_n;
+        leaderboard.bonus_pool = params.bonus_pool;
+        leaderboard.streak_bonus_bps = params.streak_bonus_bps;
+        leaderboard.max_streak_bonus_periods = params.max_streak_bonus_periods;
+        leaderboard.period_secs = params.period_secs;
+
+        emit!(LeaderboardConfigured {
+            association: leaderboard.association,
+            params,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === METER OWNER ENTERS THEIR METER IN THE BUILDING'S LEADERBOARD ===
+    pub fn join_leaderboard(ctx: Context<JoinLeaderboard>) -> Result<()> {
+        let tally = &mut ctx.accounts.tally;
+        tally.association = ctx.accounts.association.key();
+        tally.meter = ctx.accounts.meter.key();
+        tally.beneficiary = ctx.accounts.meter.beneficiary;
+        tally.bump = ctx.bumps.tally;
+        // From here on every settlement must bring the tally along
+        ctx.accounts.meter.tally = tally.key();
+
+        emit!(LeaderboardJoined {
+            association: tally.association,
+            meter: tally.meter,
+        });
+        Ok(())
+    }
+
+    // === ANYONE CRANKS: PAY THE PERIOD'S TOP N FROM THE RUNNING STANDINGS ===
+    // remaining_accounts = the beneficiary token account of each winner, in rank order
+    pub fn close_leaderboard_period<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseLeaderboardPeriod<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let leaderboard = &ctx.accounts.leaderboard;
+        let period = leaderboard.current_period;
+        require!(
+            now >= leaderboard.period_start_at + leaderboard.period_secs,
+            CustomError::LeaderboardPeriodOpen
+        );
+
+        let top_n = (leaderboard.top_n as usize).min(leaderboard.standings.len());
+        require!(
+            ctx.remaining_accounts.len() == top_n,
+            CustomError::InvalidLeaderboard
+        );
+        let share = if top_n > 0 { leaderboard.bonus_pool / top_n as u64 } else { 0 };
+
+        for (rank, (entry, beneficiary)) in leaderboard
+            .standings
+            .iter()
+            .zip(ctx.remaining_accounts)
+            .enumerate()
+        {
+            require_keys_eq!(beneficiary.key(), entry.beneficiary, CustomError::InvalidLeaderboard);
+            let amount = leaderboard.bonus_for(share, entry.streak);
+            if amount == 0 {
+                continue;
+            }
+            ctx.accounts.config.check_not_paused(entry.meter_type)?;
+            ctx.accounts.tokenomics.charge(AllocationBucket::Ecosystem, amount)?;
+            mint_jal(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                &ctx.accounts.mint_auth,
+                ctx.accounts.config.mint_auth_bump,
+                beneficiary.clone(),
+                amount,
+            )?;
+
+            emit!(LeaderboardBonusPaid {
+                association: leaderboard.association,
+                period,
+                rank: rank as u8 + 1,
+                meter: entry.meter,
+                saved_liters: entry.saved_liters,
+                streak: entry.streak,
+                amount,
+            });
+        }
+
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.current_period += 1;
+        leaderboard.period_start_at = now;
+        leaderboard.period_savers = 0;
+        leaderboard.standings.clear();
+
+        emit!(LeaderboardPeriodClosed {
+            association: leaderboard.association,
+            period,
+            winners: top_n as u8,
+        });
+        Ok(())
+    }
+
+    // === VIEW: ISSUED-BUT-UNRETIRED VOLUME LEFT IN A VINTAGE BATCH ===
+    pub fn batch_remaining(ctx: Context<BatchRemaining>) -> Result<BatchBalance> {
+        let batch = &ctx.accounts.batch;
+        Ok(BatchBalance {
+            amount: batch.unretired(),
+            liters: batch.unretired_liters(),
+        })
+    }
+
+    // === REGISTRAR BINDS (OR ROTATES) THE DEVICE KEY OF A METER ===
+    pub fn set_meter_device(ctx: Context<SetMeterDevice>, device_key: Pubkey) -> Result<()> {
+        let meter = &mut ctx.accounts.meter;
+        require!(meter.status == MeterStatus::Active, CustomError::MeterInactive);
+        meter.device_key = device_key;
+        // A fresh device starts its own counter
+        meter.device_nonce = 0;
+
+        emit!(MeterDeviceSet {
+            meter: meter.key(),
+            device_key,
+        });
+        Ok(())
+    }
+
+    // === PAUSER HALTS (OR RESUMES) ALL READINGS AND MINTING ===
+    pub fn set_global_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+
+        emit!(GlobalPauseSet {
+            paused,
+            by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // === PAUSER HALTS (OR RESUMES) ONE METER TYPE ===
+    pub fn set_meter_type_pause(
+        ctx: Context<SetPause>,
+        meter_type: MeterType,
+        paused: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        if paused {
+            config.paused_meter_types |= meter_type.bit();
+        } else {
+            config.paused_meter_types &= !meter_type.bit();
+        }
+
+        emit!(MeterTypePauseSet {
+            meter_type,
+            paused,
+            by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // === PAUSER FREEZES (OR THAWS) A SINGLE METER ===
+    pub fn set_meter_frozen(ctx: Context<SetMeterFrozen>, frozen: bool) -> Result<()> {
+        let meter = &mut ctx.accounts.meter;
+        meter.frozen = frozen;
+
+        emit!(MeterFrozenSet {
+            meter: meter.key(),
+            frozen,
+            by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER UPDATES CONFIG ===
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        residential_threshold: u64,
+        municipal_multiplier: u8,
+        jal_per_m3_bps: u32,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        emit!(ConfigUpdated {
+            old_residential_threshold: config.residential_threshold,
+            residential_threshold,
+            old_municipal_multiplier: config.municipal_multiplier,
+            municipal_multiplier,
+            old_jal_per_m3_bps: config.jal_per_m3_bps,
+            jal_per_m3_bps,
+            by: ctx.accounts.authority.key(),
+        });
+
+        config.residential_threshold = residential_threshold;
+        config.municipal_multiplier = municipal_multiplier;
+        config.jal_per_m3_bps = jal_per_m3_bps;
+
+        Ok(())
+    }
+
+    // === CONFIG MANAGER SETS UP THE SAVINGS BASELINE RULES ===
+    pub fn initialize_baseline_config(
+        ctx: Context<InitializeBaselineConfig>,
+        params: BaselineParams,
+    ) -> Result<()> {
+        let baseline = &mut ctx.accounts.baseline_config;
+        baseline.apply(params)?;
+        baseline.bump = ctx.bumps.baseline_config;
+
+        emit!(BaselineConfigUpdated {
+            params,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER TUNES THE SAVINGS BASELINE RULES ===
+    pub fn update_baseline_config(
+        ctx: Context<UpdateBaselineConfig>,
+        params: BaselineParams,
+    ) -> Result<()> {
+        ctx.accounts.baseline_config.apply(params)?;
+
+        emit!(BaselineConfigUpdated {
+            params,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === SUPER-ADMIN REGISTERS AN ORACLE KEY FOR SOME METER TYPES ===
+    pub fn register_oracle(
+        ctx: Context<RegisterOracle>,
+        oracle_key: Pubkey,
+        meter_types: Vec<MeterType>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
+        require!(!meter_types.is_empty(), CustomError::InvalidOracle);
+
+        let oracle = &mut ctx.accounts.oracle;
+        oracle.key = oracle_key;
+        oracle.meter_types = meter_types.iter().fold(0, |mask, t| mask | t.bit());
+        oracle.added_at = Clock::get()?.unix_timestamp;
+        oracle.bump = ctx.bumps.oracle;
+
+        emit!(OracleRegistered {
+            oracle: oracle_key,
+            meter_types: oracle.meter_types,
+        });
+        Ok(())
+    }
+
+    // === SUPER-ADMIN DROPS AN ORACLE FROM THE SET ===
+    pub fn remove_oracle(ctx: Context<RemoveOracle>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            CustomError::Unauthorized
+        );
+
+        emit!(OracleRemoved {
+            oracle: ctx.accounts.oracle.key,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER BOUNDS HOW FAR DEVICE CLOCKS MAY BE FROM CHAIN TIME ===
+    pub fn set_max_clock_drift(ctx: Context<UpdateConfig>, max_clock_drift_secs: i64) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(max_clock_drift_secs >= 0, CustomError::DeviceClockDrift);
+
+        let old_max_clock_drift_secs = config.max_clock_drift_secs;
+        config.max_clock_drift_secs = max_clock_drift_secs;
+
+        emit!(ClockDriftSet {
+            old_max_clock_drift_secs,
+            max_clock_drift_secs,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER TURNS ON REJECTION OF UNSIGNED METER PAYLOADS ===
+    pub fn set_require_device_signature(ctx: Context<UpdateConfig>, required: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_required = config.require_device_signature;
+        config.require_device_signature = required;
+
+        emit!(DeviceSignatureRequirementSet {
+            old_required,
+            required,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER SETS HOW LONG ACCRUALS WAIT BEFORE SETTLING ===
+    pub fn set_settlement_window(
+        ctx: Context<UpdateConfig>,
+        settlement_window_secs: i64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(settlement_window_secs >= 0, CustomError::InvalidAmount);
+
+        let old_settlement_window_secs = config.settlement_window_secs;
+        config.settlement_window_secs = settlement_window_secs;
+
+        emit!(SettlementWindowSet {
+            old_settlement_window_secs,
+            settlement_window_secs,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER SETS M-OF-N FOR A METER TYPE (0 = NO ATTESTATION) ===
+    pub fn set_oracle_quorum(
+        ctx: Context<UpdateConfig>,
+        meter_type: MeterType,
+        quorum: u8,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        let old_quorum = config.oracle_quorum[meter_type as usize];
+        config.oracle_quorum[meter_type as usize] = quorum;
+
+        emit!(OracleQuorumSet {
+            meter_type,
+            old_quorum,
+            quorum,
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER SETS THE PLAUSIBILITY RULES FOR A METER TYPE ===
+    pub fn set_plausibility_rules(
+        ctx: Context<SetPlausibilityRules>,
+        meter_type: MeterType,
+        params: PlausibilityParams,
+    ) -> Result<()> {
+        let rules = &mut ctx.accounts.plausibility;
+        rules.apply(params)?;
+        rules.meter_type = meter_type;
+        rules.bump = ctx.bumps.plausibility;
+
+        emit!(PlausibilityRulesSet {
+            meter_type,
+            params,
+            by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // === CONFIG MANAGER PINS A METER'S BASELINE (0 = FALL BACK TO DEFAULT) ===
+    pub fn set_meter_baseline(ctx: Context<SetMeterBaseline>, baseline_lpd: u64) -> Result<()> {
+        let meter = &mut ctx.accounts.meter;
+        let old_baseline_lpd = meter.baseline_lpd;
+        meter.baseline_lpd = baseline_lpd;
+        meter.baseline_updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(MeterBaselineSet {
+            meter: meter.key(),
+            old_baseline_lpd,
+            baseline_lpd,
+        });
+        Ok(())
+    }
+}
+
+// === MINT HELPER ===
+// Callers must have charged the amount against a Tokenomics bucket first
+fn mint_jal<'info>(
+    token_program: &Program<'info, Token>,
+    mint: &Account<'info, Mint>,
+    mint_auth: &UncheckedAccount<'info>,
+    mint_auth_bump: u8,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[b"mint_auth".as_ref(), &[mint_auth_bump]];
+    let signer = &[&seeds[..]];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to,
+                authority: mint_auth.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    Ok(())
+}
+
+// === LEADERBOARD HELPERS ===
+// Counts settled savings toward the meter's building leaderboard, if it joined one.
+// Returns the period they counted toward.
+fn tally_settled_reward(
+    meter: &Meter,
+    leaderboard: Option<&mut Account<Leaderboard>>,
+    tally: Option<&mut Account<SavingsTally>>,
+    liters: u64,
+) -> Result<Option<u64>> {
+    if meter.tally == Pubkey::default() {
+        return Ok(None);
+    }
+    let (Some(leaderboard), Some(tally)) = (leaderboard, tally) else {
+        return err!(CustomError::InvalidLeaderboard);
+    };
+    require_keys_eq!(tally.key(), meter.tally, CustomError::InvalidLeaderboard);
+    require_keys_eq!(tally.association, leaderboard.association, CustomError::InvalidLeaderboard);
+
+    let period = leaderboard.current_period;
+    if tally.record(period, liters, meter.beneficiary) {
+        leaderboard.period_savers += 1;
+    }
+    leaderboard.rank(LeaderboardEntry {
+        tally: tally.key(),
+        meter: tally.meter,
+        beneficiary: tally.beneficiary,
+        meter_type: meter.meter_type,
+        saved_liters: tally.saved_liters,
+        streak: tally.streak,
+    });
+    Ok(Some(period))
+}
+
+// Takes clawed-back savings off the leaderboard while their period is still open;
+// a closed period has already been paid
+fn untally_reward(
+    meter: &Meter,
+    leaderboard: Option<&mut Account<Leaderboard>>,
+    tally: Option<&mut Account<SavingsTally>>,
+    period: u64,
+    liters: u64,
+) -> Result<()> {
+    // Relinked to a replacement meter: the tally moved with it
+    if meter.tally == Pubkey::default() {
+        return Ok(());
+    }
+    let (Some(leaderboard), Some(tally)) = (leaderboard, tally) else {
+        return err!(CustomError::InvalidLeaderboard);
+    };
+    require_keys_eq!(tally.key(), meter.tally, CustomError::InvalidLeaderboard);
+    require_keys_eq!(tally.association, leaderboard.association, CustomError::InvalidLeaderboard);
+
+    if leaderboard.current_period == period && tally.forfeit(period, liters) {
+        leaderboard.lower(tally.key(), tally.saved_liters);
+    }
+    Ok(())
+}
+
+// === PROVENANCE HELPERS ===
+// Calendar year (UTC) of a unix timestamp, via Hinnant's days-to-civil
+fn vintage_year(timestamp: i64) -> u16 {
+    let z = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let year = yoe + era * 400 + if mp >= 10 { 1 } else { 0 };
+    year.clamp(0, u16::MAX as i64) as u16
+}
+
+// Marks `draws` as retired on the batch accounts passed alongside them; every batch
+// must fall inside the vintage years the retirement claims. Returns the liters drawn.
+fn draw_down_batches(
+    batches: &[AccountInfo],
+    draws: &[BatchDraw],
+    total: u64,
+    vintages: RangeInclusive<u16>,
+) -> Result<u64> {
+    require!(batches.len() == draws.len(), CustomError::InvalidRetirement);
+    let drawn = draws
+        .iter()
+        .try_fold(0u64, |sum, d| sum.checked_add(d.amount))
+        .ok_or(CustomError::InvalidRetirement)?;
+    require!(drawn == total, CustomError::InvalidRetirement);
+
+    let mut liters = 0u64;
+    for (info, draw) in batches.iter().zip(draws) {
+        require_keys_eq!(info.key(), draw.batch, CustomError::InvalidRetirement);
+        require_keys_eq!(*info.owner, crate::ID, CustomError::InvalidRetirement);
+        require!(info.is_writable, CustomError::InvalidRetirement);
+
+        let mut batch = IssuanceBatch::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(vintages.contains(&batch.vintage_year), CustomError::InvalidRetirement);
+        liters += batch.retire(draw.amount)?;
+        batch.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(liters)
+}
+
+// Mint base units for `liters` at `jal_per_m3_bps`, scaled by the mint's decimals.
+// Whatever doesn't divide into a whole base unit is carried in `remainder`.
+fn issuance_amount(liters: u64, jal_per_m3_bps: u32, decimals: u8, remainder: &mut u64) -> Result<u64> {
+    let denominator = LITERS_PER_M3 as u128 * BPS_DENOMINATOR as u128;
+    let numerator = 10u128
+        .checked_pow(decimals as u32)
+        .and_then(|scale| (liters as u128).checked_mul(scale))
+        .and_then(|n| n.checked_mul(jal_per_m3_bps as u128))
+        .and_then(|n| n.checked_add(*remainder as u128))
+        .ok_or(CustomError::Overflow)?;
+
+    let amount = u64::try_from(numerator / denominator).map_err(|_| CustomError::Overflow)?;
+    *remainder = (numerator % denominator) as u64;
+    Ok(amount)
+}
+
+// Current cumulative reading of each customer meter, checked against the district's list
+fn district_customer_readings(meters: &[AccountInfo], district: &District) -> Result<Vec<u64>> {
+    require!(meters.len() == district.customers.len(), CustomError::InvalidDistrict);
+
+    meters
+        .iter()
+        .zip(&district.customers)
+        .map(|(info, customer)| {
+            require_keys_eq!(info.key(), customer.meter, CustomError::InvalidDistrict);
+            require_keys_eq!(*info.owner, crate::ID, CustomError::InvalidDistrict);
+            let meter = Meter::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            Ok(meter.last_reading)
+        })
+        .collect()
+}
+
+struct DistrictLoss {
+    billed: u64,
+    loss_bps: u16,
+    credited: u64,
+}
+
+// NRW = water put into the district that no customer meter billed. Credits the liters
+// the district would have lost at its baseline rate but didn't, times the multiplier.
+fn district_loss(supplied: u64, metered: u64, baseline_loss_bps: u16, multiplier: u8) -> DistrictLoss {
+    let billed = metered.min(supplied);
+    let lost = supplied - billed;
+    let loss_bps = if supplied == 0 {
+        0
+    } else {
+        (lost as u128 * BPS_DENOMINATOR as u128 / supplied as u128) as u16
+    };
+
+    let avoided = if supplied > 0 && loss_bps < baseline_loss_bps {
+        (supplied as u128 * (baseline_loss_bps - loss_bps) as u128 / BPS_DENOMINATOR as u128) as u64
+    } else {
+        0
+    };
+    DistrictLoss {
+        billed,
+        loss_bps,
+        credited: (avoided as u128 * multiplier as u128 / 100) as u64,
+    }
+}
+
+// === ORACLE ATTESTATION HELPERS ===
+fn reading_attestation_message(meter: &Pubkey, sequence: u64, reading: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(READING_ATTESTATION_DOMAIN.len() + 32 + 8 + 8);
+    message.extend_from_slice(READING_ATTESTATION_DOMAIN);
+    message.extend_from_slice(meter.as_ref());
+    message.extend_from_slice(&sequence.to_le_bytes());
+    message.extend_from_slice(&reading.to_le_bytes());
+    message
+}
+
+// Keys whose signature over `message` was verified by an Ed25519 precompile
+// instruction earlier in this transaction. The runtime fails the whole tx if
+// any precompile signature is bad, so we only need to read what it checked.
+fn ed25519_signers(ix_sysvar: &AccountInfo, message: &[u8]) -> Result<Vec<Pubkey>> {
+    let mut signers = Vec::new();
+    let current = load_current_index_checked(ix_sysvar)?;
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index as usize, ix_sysvar)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        collect_ed25519_signers(&ix.data, message, &mut signers)?;
+    }
+    Ok(signers)
+}
+
+// Adds the keys one Ed25519 precompile instruction verified over `message`
+fn collect_ed25519_signers(data: &[u8], message: &[u8], signers: &mut Vec<Pubkey>) -> Result<()> {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    // Offsets pointing at the precompile's own data
+    const THIS_IX: u16 = u16::MAX;
+
+    let read_u16 = |data: &[u8], at: usize| -> Result<u16> {
+        data.get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or_else(|| error!(CustomError::InvalidSignatureInstruction))
+    };
+
+    let count = *data.first().ok_or(CustomError::InvalidSignatureInstruction)? as usize;
+    for i in 0..count {
+        let at = OFFSETS_START + i * OFFSETS_LEN;
+        let sig_ix = read_u16(data, at + 2)?;
+        let key_offset = read_u16(data, at + 4)? as usize;
+        let key_ix = read_u16(data, at + 6)?;
+        let msg_offset = read_u16(data, at + 8)? as usize;
+        let msg_len = read_u16(data, at + 10)? as usize;
+        let msg_ix = read_u16(data, at + 12)?;
+
+        // Data living in other instructions is not something we can vouch for
+        if sig_ix != THIS_IX || key_ix != THIS_IX || msg_ix != THIS_IX {
+            continue;
+        }
+        let key = data
+            .get(key_offset..key_offset + 32)
+            .ok_or(CustomError::InvalidSignatureInstruction)?;
+        let signed = data
+            .get(msg_offset..msg_offset + msg_len)
+            .ok_or(CustomError::InvalidSignatureInstruction)?;
+
+        if signed == message {
+            let key = Pubkey::try_from(key).map_err(|_| CustomError::InvalidSignatureInstruction)?;
+            if !signers.contains(&key) {
+                signers.push(key);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Distinct registered oracles (passed as remaining accounts) that signed
+fn count_oracle_attestations(
+    oracles: &[AccountInfo],
+    signers: &[Pubkey],
+    meter_type: MeterType,
+) -> Result<usize> {
+    let mut counted: Vec<Pubkey> = Vec::new();
+    for info in oracles {
+        require_keys_eq!(*info.owner, crate::ID, CustomError::InvalidOracle);
+        let oracle = Oracle::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+
+        if oracle.meter_types & meter_type.bit() != 0
+            && signers.contains(&oracle.key)
+            && !counted.contains(&oracle.key)
+        {
+            counted.push(oracle.key);
+        }
+    }
+    Ok(counted.len())
+}
+
+// === DEVICE PAYLOAD ===
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MeterPayload {
+    pub meter_id: String,
+    pub reading: u64,
+    pub unit: VolumeUnit,
+    pub device_timestamp: i64,
+    pub nonce: u64,
+}
+
+impl MeterPayload {
+    // Exact bytes the device signs: domain tag followed by the borsh-encoded payload
+    pub fn signing_message(&self) -> Result<Vec<u8>> {
+        let mut message = DEVICE_PAYLOAD_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+// === ACCOUNTS ===
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, MintConfig>,
+
+    /// CHECK: PDA used only as the mint authority, never read
+    #[account(seeds = [b"mint_auth"], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    /// CHECK: created and initialized as an SPL mint in `initialize`
+    #[account(mut, seeds = [b"mint"], bump)]
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTokenomics<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Tokenomics::INIT_SPACE,
+        seeds = [b"tokenomics"],
+        bump
+    )]
+    pub tokenomics: Account<'info, Tokenomics>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintAllocation<'info> {
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Account<'info, Tokenomics>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    /// CHECK: PDA mint authority, only signs the `mint_to` CPI
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BucketHeadroom<'info> {
+    #[account(seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Account<'info, Tokenomics>,
+}
+
+#[derive(Accounts)]
+#[instruction(schedule_id: u64, beneficiary: Pubkey)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + VestingSchedule::INIT_SPACE,
+        seeds = [b"vesting", beneficiary.as_ref(), &schedule_id.to_le_bytes()],
+        bump
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = vesting,
+        seeds = [b"vesting_vault", vesting.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Account<'info, Tokenomics>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    /// CHECK: PDA mint authority, only signs the `mint_to` CPI
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        has_one = beneficiary,
+        has_one = vault,
+        seeds = [b"vesting", beneficiary.key().as_ref(), &vesting.schedule_id.to_le_bytes()],
+        bump = vesting.bump
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vault.mint,
+        token::authority = beneficiary,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub beneficiary: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeUnvested<'info> {
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"vesting", vesting.beneficiary.as_ref(), &vesting.schedule_id.to_le_bytes()],
+        bump = vesting.bump
+    )]
+    pub vesting: Account<'info, VestingSchedule>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Account<'info, Tokenomics>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Retire<'info> {
+    #[account(
+        init_if_needed,
+        payer = retiree,
+        space = 8 + RetireeAccount::INIT_SPACE,
+        seeds = [b"retiree", retiree.key().as_ref()],
+        bump
+    )]
+    pub retiree_account: Account<'info, RetireeAccount>,
+
+    #[account(
+        init,
+        payer = retiree,
+        space = 8 + Retirement::INIT_SPACE,
+        seeds = [
+            b"retirement",
+            retiree.key().as_ref(),
+            &retiree_account.retirement_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub retirement: Account<'info, Retirement>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = config.mint,
+        token::authority = retiree,
+    )]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub retiree: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct IssueRetirementCertificate<'info> {
+    #[account(
+        has_one = retiree,
+        seeds = [b"retirement", retiree.key().as_ref(), &retirement.index.to_le_bytes()],
+        bump = retirement.bump
+    )]
+    pub retirement: Account<'info, Retirement>,
+
+    // One certificate per retirement
+    #[account(
+        init,
+        payer = retiree,
+        space = 8 + RetirementCertificate::INIT_SPACE,
+        seeds = [b"certificate", retirement.key().as_ref()],
+        bump
+    )]
+    pub certificate: Account<'info, RetirementCertificate>,
+
+    #[account(
+        init,
+        payer = retiree,
+        mint::decimals = 0,
+        mint::authority = mint_auth,
+        mint::freeze_authority = mint_auth,
+        seeds = [b"certificate_mint", retirement.key().as_ref()],
+        bump
+    )]
+    pub certificate_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = retiree,
+        associated_token::mint = certificate_mint,
+        associated_token::authority = retiree,
+    )]
+    pub certificate_token: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    /// CHECK: PDA mint authority, signs the certificate mint/freeze CPIs
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub retiree: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [b"role".as_ref(), &[role as u8], holder.as_ref()],
+        bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"role".as_ref(), &[role_assignment.role as u8], role_assignment.holder.as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(meter_id: String)]
+pub struct RegisterMeter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Meter::INIT_SPACE,
+        seeds = [b"meter", meter_id.as_bytes()],
+        bump
+    )]
+    pub meter: Account<'info, Meter>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(token::mint = config.mint)]
+    pub beneficiary: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateMeter<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMeterRegister<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_meter_id: String)]
+pub struct ReplaceMeter<'info> {
+    #[account(mut, seeds = [b"meter", old_meter.meter_id.as_bytes()], bump = old_meter.bump)]
+    pub old_meter: Box<Account<'info, Meter>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Meter::INIT_SPACE,
+        seeds = [b"meter", new_meter_id.as_bytes()],
+        bump
+    )]
+    pub new_meter: Box<Account<'info, Meter>>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RelinkMeter<'info> {
+    #[account(mut, seeds = [b"meter", old_meter.meter_id.as_bytes()], bump = old_meter.bump)]
+    pub old_meter: Box<Account<'info, Meter>>,
+
+    #[account(mut, address = old_meter.replaced_by @ CustomError::MeterNotReplaced)]
+    pub new_meter: Box<Account<'info, Meter>>,
+
+    #[account(mut)]
+    pub facility: Option<Box<Account<'info, Facility>>>,
+
+    #[account(mut)]
+    pub district: Option<Box<Account<'info, District>>>,
+
+    #[account(mut)]
+    pub tally: Option<Box<Account<'info, SavingsTally>>>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferMeter<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(token::mint = config.mint)]
+    pub new_beneficiary: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(payload: MeterPayload)]
+pub struct RecordReading<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WaterReading::INIT_SPACE,
+        seeds = [b"reading", meter.key().as_ref(), &meter.reading_count.to_le_bytes()],
+        bump
+    )]
+    pub reading: Account<'info, WaterReading>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(seeds = [b"baseline_config"], bump = baseline_config.bump)]
+    pub baseline_config: Account<'info, BaselineConfig>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Account<'info, Tokenomics>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RewardLedger::INIT_SPACE,
+        seeds = [b"reward_ledger", meter.key().as_ref(), meter.owner.as_ref()],
+        bump
+    )]
+    pub reward_ledger: Box<Account<'info, RewardLedger>>,
+
+    #[account(
+        seeds = [b"plausibility".as_ref(), &[meter.meter_type as u8]],
+        bump = plausibility.bump
+    )]
+    pub plausibility: Box<Account<'info, PlausibilityRules>>,
+
+    // Read for decimals only; nothing is minted here
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Reporter as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: address-checked instructions sysvar, read for Ed25519 attestations
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"reading", reading.meter.as_ref(), &reading.sequence.to_le_bytes()],
+        bump = reading.bump
+    )]
+    pub reading: Account<'info, WaterReading>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_ledger", reading.meter.as_ref(), reading.reward_owner.as_ref()],
+        bump = reward_ledger.bump
+    )]
+    pub reward_ledger: Account<'info, RewardLedger>,
+
+    #[account(address = reading.meter)]
+    pub meter: Box<Account<'info, Meter>>,
+
+    // Vintage is the calendar year of the (clock-checked) device timestamp
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IssuanceBatch::INIT_SPACE,
+        seeds = [
+            b"batch".as_ref(),
+            &[reading.meter_type as u8],
+            &meter.region.to_le_bytes(),
+            &vintage_year(reading.device_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub batch: Box<Account<'info, IssuanceBatch>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    // Required once the meter has joined a leaderboard
+    #[account(mut)]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+
+    #[account(mut)]
+    pub tally: Option<Box<Account<'info, SavingsTally>>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"reward_ledger", meter.key().as_ref(), owner.key().as_ref()],
+        bump = reward_ledger.bump
+    )]
+    pub reward_ledger: Box<Account<'info, RewardLedger>>,
+
+    #[account(seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Box<Account<'info, Meter>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, MintConfig>>,
+
+    /// CHECK: PDA mint authority, only signs the `mint_to` CPI
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeReading<'info> {
+    #[account(
+        mut,
+        seeds = [b"reading", reading.meter.as_ref(), &reading.sequence.to_le_bytes()],
+        bump = reading.bump
+    )]
+    pub reading: Account<'info, WaterReading>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_ledger", reading.meter.as_ref(), reading.reward_owner.as_ref()],
+        bump = reward_ledger.bump
+    )]
+    pub reward_ledger: Account<'info, RewardLedger>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Auditor as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"reading", meter.key().as_ref(), &reading.sequence.to_le_bytes()],
+        bump = reading.bump
+    )]
+    pub reading: Box<Account<'info, WaterReading>>,
+
+    #[account(seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Box<Account<'info, Meter>>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_ledger", meter.key().as_ref(), reading.reward_owner.as_ref()],
+        bump = reward_ledger.bump
+    )]
+    pub reward_ledger: Box<Account<'info, RewardLedger>>,
+
+    // Booked only if the dispute is resolved in the reading's favour
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + IssuanceBatch::INIT_SPACE,
+        seeds = [
+            b"batch".as_ref(),
+            &[reading.meter_type as u8],
+            &meter.region.to_le_bytes(),
+            &vintage_year(reading.device_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub batch: Box<Account<'info, IssuanceBatch>>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Box<Account<'info, Tokenomics>>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Auditor as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Box<Account<'info, RoleAssignment>>,
+
+    // Required once the meter has joined a leaderboard
+    #[account(mut)]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+
+    #[account(mut)]
+    pub tally: Option<Box<Account<'info, SavingsTally>>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveQuarantine<'info> {
+    #[account(
+        mut,
+        seeds = [b"reading", reading.meter.as_ref(), &reading.sequence.to_le_bytes()],
+        bump = reading.bump
+    )]
+    pub reading: Account<'info, WaterReading>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Auditor as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClawbackReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"reading", meter.key().as_ref(), &reading.sequence.to_le_bytes()],
+        bump = reading.bump
+    )]
+    pub reading: Box<Account<'info, WaterReading>>,
+
+    #[account(seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Box<Account<'info, Meter>>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_ledger", meter.key().as_ref(), reading.reward_owner.as_ref()],
+        bump = reward_ledger.bump
+    )]
+    pub reward_ledger: Box<Account<'info, RewardLedger>>,
+
+    // Required only for settled rewards, the ones already booked
+    #[account(
+        mut,
+        seeds = [
+            b"batch".as_ref(),
+            &[reading.meter_type as u8],
+            &meter.region.to_le_bytes(),
+            &vintage_year(reading.device_timestamp).to_le_bytes()
+        ],
+        bump = batch.bump
+    )]
+    pub batch: Option<Box<Account<'info, IssuanceBatch>>>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Box<Account<'info, Tokenomics>>,
+
+    // Required when a settled reward still counts toward the meter's open leaderboard period
+    #[account(mut)]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+
+    #[account(mut)]
+    pub tally: Option<Box<Account<'info, SavingsTally>>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, MintConfig>>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(meter_type: MeterType)]
+pub struct SetPlausibilityRules<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PlausibilityRules::INIT_SPACE,
+        seeds = [b"plausibility".as_ref(), &[meter_type as u8]],
+        bump
+    )]
+    pub plausibility: Account<'info, PlausibilityRules>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBaselineConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BaselineConfig::INIT_SPACE,
+        seeds = [b"baseline_config"],
+        bump
+    )]
+    pub baseline_config: Account<'info, BaselineConfig>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBaselineConfig<'info> {
+    #[account(mut, seeds = [b"baseline_config"], bump = baseline_config.bump)]
+    pub baseline_config: Account<'info, BaselineConfig>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(oracle_key: Pubkey)]
+pub struct RegisterOracle<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Oracle::INIT_SPACE,
+        seeds = [b"oracle", oracle_key.as_ref()],
+        bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveOracle<'info> {
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"oracle", oracle.key.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(facility_id: String)]
+pub struct RegisterFacility<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Facility::INIT_SPACE,
+        seeds = [b"facility", facility_id.as_bytes()],
+        bump
+    )]
+    pub facility: Account<'info, Facility>,
+
+    #[account(seeds = [b"meter", inflow_meter.meter_id.as_bytes()], bump = inflow_meter.bump)]
+    pub inflow_meter: Account<'info, Meter>,
+
+    #[account(seeds = [b"meter", outflow_meter.meter_id.as_bytes()], bump = outflow_meter.bump)]
+    pub outflow_meter: Account<'info, Meter>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(token::mint = config.mint)]
+    pub beneficiary: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(region: u16)]
+pub struct SetQualityStandard<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + QualityStandard::INIT_SPACE,
+        seeds = [b"quality_standard".as_ref(), &region.to_le_bytes()],
+        bump
+    )]
+    pub quality_standard: Account<'info, QualityStandard>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u64)]
+pub struct SubmitQualityReport<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + QualityReport::INIT_SPACE,
+        seeds = [b"quality".as_ref(), facility.key().as_ref(), &period.to_le_bytes()],
+        bump
+    )]
+    pub quality_report: Account<'info, QualityReport>,
+
+    #[account(seeds = [b"facility", facility.facility_id.as_bytes()], bump = facility.bump)]
+    pub facility: Account<'info, Facility>,
+
+    #[account(
+        seeds = [b"quality_standard".as_ref(), &facility.region.to_le_bytes()],
+        bump = quality_standard.bump
+    )]
+    pub quality_standard: Account<'info, QualityStandard>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Reporter as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(period: u64)]
+pub struct ClaimReclamation<'info> {
+    #[account(
+        mut,
+        has_one = inflow_meter,
+        has_one = outflow_meter,
+        has_one = beneficiary,
+        seeds = [b"facility", facility.facility_id.as_bytes()],
+        bump = facility.bump
+    )]
+    pub facility: Box<Account<'info, Facility>>,
+
+    // The claimed period's report; may be omitted when the claim only attributes volume
+    pub quality_report: Option<Box<Account<'info, QualityReport>>>,
+
+    pub inflow_meter: Box<Account<'info, Meter>>,
+
+    pub outflow_meter: Box<Account<'info, Meter>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, MintConfig>>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Box<Account<'info, Tokenomics>>,
+
+    // Vintage is the year the claimed period starts in
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IssuanceBatch::INIT_SPACE,
+        seeds = [
+            b"batch".as_ref(),
+            &[MeterType::Industrial as u8],
+            &facility.region.to_le_bytes(),
+            &vintage_year(facility.period_start(period)).to_le_bytes()
+        ],
+        bump
+    )]
+    pub batch: Box<Account<'info, IssuanceBatch>>,
+
+    /// CHECK: PDA mint authority, only signs the `mint_to` CPI
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub beneficiary: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(district_id: String)]
+pub struct RegisterDistrict<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + District::INIT_SPACE,
+        seeds = [b"district", district_id.as_bytes()],
+        bump
+    )]
+    pub district: Account<'info, District>,
+
+    #[account(seeds = [b"meter", inflow_meter.meter_id.as_bytes()], bump = inflow_meter.bump)]
+    pub inflow_meter: Account<'info, Meter>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(token::mint = config.mint)]
+    pub beneficiary: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDistrictMeters<'info> {
+    #[account(mut, seeds = [b"district", district.district_id.as_bytes()], bump = district.bump)]
+    pub district: Account<'info, District>,
+
+    #[account(seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDistrictPeriod<'info> {
+    #[account(
+        mut,
+        has_one = inflow_meter,
+        has_one = beneficiary,
+        seeds = [b"district", district.district_id.as_bytes()],
+        bump = district.bump
+    )]
+    pub district: Box<Account<'info, District>>,
+
+    pub inflow_meter: Box<Account<'info, Meter>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, MintConfig>>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Box<Account<'info, Tokenomics>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IssuanceBatch::INIT_SPACE,
+        seeds = [
+            b"batch".as_ref(),
+            &[MeterType::Municipal as u8],
+            &district.region.to_le_bytes(),
+            &vintage_year(inflow_meter.last_reading_at).to_le_bytes()
+        ],
+        bump
+    )]
+    pub batch: Box<Account<'info, IssuanceBatch>>,
+
+    /// CHECK: PDA mint authority, only signs the `mint_to` CPI
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub beneficiary: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(building: Pubkey)]
+pub struct CreateAssociation<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Association::INIT_SPACE,
+        seeds = [b"association", building.as_ref()],
+        bump
+    )]
+    pub association: Account<'info, Association>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = association,
+        seeds = [b"association_treasury", association.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAssociation<'info> {
+    #[account(
+        mut,
+        has_one = manager,
+        seeds = [b"association", association.building.as_ref()],
+        bump = association.bump
+    )]
+    pub association: Account<'info, Association>,
+
+    pub manager: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAssociationTreasury<'info> {
+    #[account(
+        mut,
+        has_one = manager,
+        has_one = treasury,
+        seeds = [b"association", association.building.as_ref()],
+        bump = association.bump
+    )]
+    pub association: Account<'info, Association>,
+
+    #[account(mut)]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = treasury.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub manager: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemForFeeDiscount<'info> {
+    #[account(
+        mut,
+        has_one = treasury,
+        seeds = [b"association", association.building.as_ref()],
+        bump = association.bump
+    )]
+    pub association: Box<Account<'info, Association>>,
+
+    #[account(mut)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    // Only owners of a meter in the building count as residents
+    #[account(
+        seeds = [b"meter", meter.meter_id.as_bytes()],
+        bump = meter.bump,
+        constraint = meter.building == association.building @ CustomError::NotAResident,
+        constraint = meter.owner == resident.key() @ CustomError::NotAResident
+    )]
+    pub meter: Box<Account<'info, Meter>>,
+
+    #[account(
+        init_if_needed,
+        payer = resident,
+        space = 8 + FeeCredit::INIT_SPACE,
+        seeds = [b"fee_credit", association.key().as_ref(), resident.key().as_ref()],
+        bump
+    )]
+    pub fee_credit: Box<Account<'info, FeeCredit>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = resident,
+    )]
+    pub source: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = treasury.mint)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub resident: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyFeeCredit<'info> {
+    #[account(
+        has_one = manager,
+        seeds = [b"association", association.building.as_ref()],
+        bump = association.bump
+    )]
+    pub association: Account<'info, Association>,
+
+    #[account(
+        mut,
+        has_one = association,
+        seeds = [b"fee_credit", association.key().as_ref(), fee_credit.resident.as_ref()],
+        bump = fee_credit.bump
+    )]
+    pub fee_credit: Account<'info, FeeCredit>,
+
+    pub manager: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateFeeMandate<'info> {
+    #[account(
+        init,
+        payer = resident,
+        space = 8 + FeeMandate::INIT_SPACE,
+        seeds = [b"fee_mandate", association.key().as_ref(), resident.key().as_ref()],
+        bump
+    )]
+    pub mandate: Account<'info, FeeMandate>,
+
+    #[account(
+        has_one = treasury,
+        seeds = [b"association", association.building.as_ref()],
+        bump = association.bump
+    )]
+    pub association: Account<'info, Association>,
+
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"meter", meter.meter_id.as_bytes()],
+        bump = meter.bump,
+        constraint = meter.building == association.building @ CustomError::NotAResident,
+        constraint = meter.owner == resident.key() @ CustomError::NotAResident
+    )]
+    pub meter: Account<'info, Meter>,
+
+    // Approving would silently replace another program's delegation
+    #[account(
+        mut,
+        token::mint = treasury.mint,
+        token::authority = resident,
+        constraint = source.delegate.is_none() @ CustomError::SourceAlreadyDelegated
+    )]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub resident: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteFeeDebit<'info> {
+    #[account(
+        mut,
+        has_one = association,
+        has_one = source,
+        seeds = [b"fee_mandate", association.key().as_ref(), mandate.resident.as_ref()],
+        bump = mandate.bump
+    )]
+    pub mandate: Box<Account<'info, FeeMandate>>,
+
+    #[account(
+        mut,
+        has_one = treasury,
+        seeds = [b"association", association.building.as_ref()],
+        bump = association.bump
+    )]
+    pub association: Box<Account<'info, Association>>,
+
+    #[account(mut)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub source: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = treasury.mint)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeCredit::INIT_SPACE,
+        seeds = [b"fee_credit", association.key().as_ref(), mandate.resident.as_ref()],
+        bump
+    )]
+    pub fee_credit: Box<Account<'info, FeeCredit>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeReceipt::INIT_SPACE,
+        seeds = [
+            b"fee_receipt".as_ref(),
+            mandate.key().as_ref(),
+            &mandate.created_at.to_le_bytes(),
+            &mandate.debit_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub receipt: Box<Account<'info, FeeReceipt>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageFeeMandate<'info> {
+    #[account(
+        mut,
+        has_one = resident,
+        seeds = [b"fee_mandate", mandate.association.as_ref(), resident.key().as_ref()],
+        bump = mandate.bump
+    )]
+    pub mandate: Account<'info, FeeMandate>,
+
+    pub resident: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelFeeMandate<'info> {
+    #[account(
+        mut,
+        close = resident,
+        has_one = resident,
+        has_one = source,
+        seeds = [b"fee_mandate", mandate.association.as_ref(), resident.key().as_ref()],
+        bump = mandate.bump
+    )]
+    pub mandate: Account<'info, FeeMandate>,
+
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub resident: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureLeaderboard<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard", association.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(seeds = [b"association", association.building.as_ref()], bump = association.bump)]
+    pub association: Account<'info, Association>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinLeaderboard<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SavingsTally::INIT_SPACE,
+        seeds = [b"tally", association.key().as_ref(), meter.key().as_ref()],
+        bump
+    )]
+    pub tally: Account<'info, SavingsTally>,
+
+    #[account(seeds = [b"association", association.building.as_ref()], bump = association.bump)]
+    pub association: Account<'info, Association>,
+
+    #[account(
+        mut,
+        seeds = [b"meter", meter.meter_id.as_bytes()],
+        bump = meter.bump,
+        has_one = owner,
+        constraint = meter.building == association.building @ CustomError::NotAResident,
+        constraint = meter.tally == Pubkey::default() @ CustomError::InvalidLeaderboard
+    )]
+    pub meter: Account<'info, Meter>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLeaderboardPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"leaderboard", leaderboard.association.as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Box<Account<'info, Leaderboard>>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Box<Account<'info, MintConfig>>,
+
+    #[account(mut, seeds = [b"tokenomics"], bump = tokenomics.bump)]
+    pub tokenomics: Box<Account<'info, Tokenomics>>,
+
+    /// CHECK: PDA mint authority, only signs the `mint_to` CPI
+    #[account(seeds = [b"mint_auth"], bump = config.mint_auth_bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Box<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BatchRemaining<'info> {
+    pub batch: Account<'info, IssuanceBatch>,
+}
+
+#[derive(Accounts)]
+pub struct SetMeterDevice<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::MeterRegistrar as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MintConfig>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Pauser as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMeterFrozen<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::Pauser as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMeterBaseline<'info> {
+    #[account(mut, seeds = [b"meter", meter.meter_id.as_bytes()], bump = meter.bump)]
+    pub meter: Account<'info, Meter>,
+
+    #[account(
+        seeds = [b"role".as_ref(), &[Role::ConfigManager as u8], authority.key().as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, RoleAssignment>,
+
+    pub authority: Signer<'info>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Meter {
+    #[max_len(32)]
+    pub meter_id: String,
+    pub meter_type: MeterType,
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub building: Pubkey,
+    pub region: u16,
+    pub install_date: i64,
+    pub status: MeterStatus,
+    pub registered_at: i64,
+    pub reading_count: u64,
+    pub last_reading: u64,
+    pub last_reading_at: i64,
+    pub baseline_lpd: u64,
+    pub baseline_updated_at: i64,
+    pub period_start_reading: u64,
+    pub period_start_at: i64,
+    pub device_key: Pubkey, // default = no device bound
+    pub device_nonce: u64,
+    pub frozen: bool,
+    pub issuance_remainder: u64, // sub-base-unit issuance carried to the next mint
+    // Rolling (EWMA) stats of the liters/day rate between accepted readings
+    pub flow_mean_lpd: u64,
+    pub flow_var: u128,
+    pub flow_samples: u32,
+    pub register_digits: u8, // 0 = register never rolls over
+    pub register_offset: i64, // liters added to the device register for the continuous total
+    pub last_register: u64, // device register at the last reading, in liters
+    pub replaces: Pubkey, // previous physical meter, default if none
+    pub replaced_by: Pubkey, // successor once swapped out
+    pub tally: Pubkey, // leaderboard tally, default if the meter hasn't joined one
+    pub bump: u8,
+}
+
+impl Meter {
+    // Liters/day this meter is measured against
+    pub fn effective_baseline(&self, baseline: &BaselineConfig) -> u64 {
+        if self.baseline_lpd > 0 {
+            self.baseline_lpd
+        } else {
+            baseline.default_baseline_lpd
+        }
+    }
+
+    // Liters saved over the open period, None while the period is still too short
+    pub fn period_savings(&self, baseline: &BaselineConfig, reading: u64, now: i64) -> Option<u64> {
+        if self.reading_count == 0 {
+            return None;
+        }
+        let elapsed = now.saturating_sub(self.period_start_at);
+        if elapsed < baseline.min_period_secs {
+            return None;
+        }
+
+        // A silent meter can't bank an unbounded baseline
+        let credited_secs = elapsed.min(baseline.max_period_secs) as u128;
+        let expected = self.effective_baseline(baseline) as u128 * credited_secs
+            / SECONDS_PER_DAY as u128;
+        let consumed = reading.saturating_sub(self.period_start_reading) as u128;
+
+        Some(expected.saturating_sub(consumed).min(u64::MAX as u128) as u64)
+    }
+
+    // Continuous reading for a device register value, plus the offset to store with it.
+    // A lower register on a rollover-capable meter is a wrap; the flow guard catches
+    // a genuine rollback masquerading as one.
+    pub fn continuous_reading(&self, register: u64, unit: VolumeUnit) -> Result<(u64, i64)> {
+        let mut offset = self.register_offset;
+        if self.reading_count > 0 && self.register_digits > 0 && register < self.last_register {
+            let capacity = unit.to_liters(10u64.pow(self.register_digits as u32))?;
+            // Anything else is a reset or tamper, not a wrap
+            require!(
+                self.last_register >= capacity - capacity / REGISTER_WRAP_WINDOW_DIVISOR,
+                CustomError::ReadingBelowPrevious
+            );
+            let capacity = i64::try_from(capacity).map_err(|_| CustomError::Overflow)?;
+            offset = offset.checked_add(capacity).ok_or(CustomError::Overflow)?;
+        }
+        let reading = u64::try_from(offset as i128 + register as i128)
+            .map_err(|_| CustomError::Overflow)?;
+        Ok((reading, offset))
+    }
+
+    // Liters/day implied by moving to `reading` at `now`, None on the first reading
+    pub fn flow_rate_lpd(&self, reading: u64, now: i64) -> Option<u64> {
+        let interval = now.saturating_sub(self.last_reading_at);
+        if self.reading_count == 0 || interval <= 0 {
+            return None;
+        }
+        let delta = reading.saturating_sub(self.last_reading) as u128;
+        Some((delta * SECONDS_PER_DAY as u128 / interval as u128).min(u64::MAX as u128) as u64)
+    }
+
+    // Folds an accepted reading into the rolling mean/variance
+    pub fn observe_flow(&mut self, rules: &PlausibilityRules, reading: u64, now: i64) {
+        let Some(rate) = self.flow_rate_lpd(reading, now) else {
+            return;
+        };
+        if self.flow_samples == 0 {
+            self.flow_mean_lpd = rate;
+            self.flow_var = 0;
+        } else {
+            // mean += α·diff;  var = (1 - α)·(var + α·diff²)
+            let weight = rules.ewma_weight_bps as i128;
+            let denom = BPS_DENOMINATOR as i128;
+            let diff = rate as i128 - self.flow_mean_lpd as i128;
+            let mean = self.flow_mean_lpd as i128 + diff * weight / denom;
+            let spread = diff.unsigned_abs().saturating_mul(diff.unsigned_abs());
+            let var = self
+                .flow_var
+                .saturating_add(spread.saturating_mul(weight as u128) / denom as u128)
+                .saturating_mul((denom - weight) as u128)
+                / denom as u128;
+            self.flow_mean_lpd = mean.clamp(0, u64::MAX as i128) as u64;
+            self.flow_var = var;
+        }
+        self.flow_samples = self.flow_samples.saturating_add(1);
+    }
+
+    // Re-baselines if due, then opens a new period at this reading
+    pub fn close_period(&mut self, baseline: &BaselineConfig, reading: u64, now: i64) {
+        let elapsed = now.saturating_sub(self.period_start_at);
+        let rebaseline_due = baseline.rebaseline_interval_secs > 0
+            && now.saturating_sub(self.baseline_updated_at) >= baseline.rebaseline_interval_secs;
+
+        if self.reading_count > 0 && elapsed > 0 && rebaseline_due {
+            let consumed = reading.saturating_sub(self.period_start_reading) as u128;
+            let observed = consumed * SECONDS_PER_DAY as u128 / elapsed as u128;
+            let current = self.effective_baseline(baseline) as u128;
+            let weight = baseline.rebaseline_weight_bps as u128;
+
+            let blended = (current * (BPS_DENOMINATOR as u128 - weight) + observed * weight)
+                / BPS_DENOMINATOR as u128;
+            let blended = blended.min(u64::MAX as u128) as u64;
+
+            // Optionally only ever ratchet the baseline down
+            if baseline.allow_upward_rebaseline || blended < current as u64 {
+                self.baseline_lpd = blended;
+            }
+            self.baseline_updated_at = now;
+        }
+
+        self.period_start_reading = reading;
+        self.period_start_at = now;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BaselineConfig {
+    pub default_baseline_lpd: u64,
+    pub min_period_secs: i64,
+    pub max_period_secs: i64,
+    pub rebaseline_interval_secs: i64,
+    pub rebaseline_weight_bps: u16,
+    pub allow_upward_rebaseline: bool,
+    pub bump: u8,
+}
+
+impl BaselineConfig {
+    pub fn apply(&mut self, params: BaselineParams) -> Result<()> {
+        require!(params.min_period_secs > 0, CustomError::InvalidBaselineConfig);
+        require!(
+            params.max_period_secs >= params.min_period_secs,
+            CustomError::InvalidBaselineConfig
+        );
+        require!(params.rebaseline_interval_secs >= 0, CustomError::InvalidBaselineConfig);
+        require!(
+            params.rebaseline_weight_bps as u64 <= BPS_DENOMINATOR,
+            CustomError::InvalidBaselineConfig
+        );
+
+        self.default_baseline_lpd = params.default_baseline_lpd;
+        self.min_period_secs = params.min_period_secs;
+        self.max_period_secs = params.max_period_secs;
+        self.rebaseline_interval_secs = params.rebaseline_interval_secs;
+        self.rebaseline_weight_bps = params.rebaseline_weight_bps;
+        self.allow_upward_rebaseline = params.allow_upward_rebaseline;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BaselineParams {
+    pub default_baseline_lpd: u64,
+    pub min_period_secs: i64,
+    pub max_period_secs: i64,
+    pub rebaseline_interval_secs: i64,
+    pub rebaseline_weight_bps: u16,
+    pub allow_upward_rebaseline: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WaterReading {
+    pub meter: Pubkey,
+    pub sequence: u64,
+    pub reading: u64, // liters
+    pub raw_reading: u64,
+    pub unit: VolumeUnit,
+    pub previous_reading: u64,
+    pub timestamp: i64,
+    pub meter_type: MeterType,
+    pub authority: Pubkey,
+    pub device_timestamp: i64,
+    pub nonce: u64,
+    pub device_signed: bool,
+    pub reward_liters: u64,
+    pub reward: u64,
+    pub reward_owner: Pubkey, // meter owner at accrual, whose ledger holds the reward
+    pub reward_status: RewardStatus,
+    pub claim_epoch: u64, // ledger's claim epoch when settled; behind it = already minted
+    pub tallied_period: Option<u64>, // leaderboard period the settled savings counted toward
+    pub status: ReadingStatus,
+    pub dispute_reason: u16,
+    pub disputed_by: Pubkey,
+    pub disputed_at: i64,
+    pub bump: u8,
+}
+
+// Per-MeterType sanity bounds on incoming readings (0 disables a bound)
+#[account]
+#[derive(InitSpace)]
+pub struct PlausibilityRules {
+    pub meter_type: MeterType,
+    pub min_interval_secs: i64,
+    pub max_flow_lph: u64, // liters per hour between consecutive readings
+    pub max_z_score_x100: u32,
+    pub min_samples: u32, // rolling stats needed before the z-score applies
+    pub ewma_weight_bps: u16,
+    pub action: AnomalyAction,
+    pub bump: u8,
+}
+
+impl PlausibilityRules {
+    pub fn apply(&mut self, params: PlausibilityParams) -> Result<()> {
+        require!(
+            params.min_interval_secs >= 0
+                && params.ewma_weight_bps > 0
+                && params.ewma_weight_bps as u64 <= BPS_DENOMINATOR,
+            CustomError::InvalidPlausibilityRules
+        );
+
+        self.min_interval_secs = params.min_interval_secs;
+        self.max_flow_lph = params.max_flow_lph;
+        self.max_z_score_x100 = params.max_z_score_x100;
+        self.min_samples = params.min_samples;
+        self.ewma_weight_bps = params.ewma_weight_bps;
+        self.action = params.action;
+        Ok(())
+    }
+
+    // First rule `reading` breaks, if any. Rollbacks are rejected outright earlier.
+    pub fn check(&self, meter: &Meter, reading: u64, now: i64) -> Option<AnomalyKind> {
+        if meter.reading_count == 0 {
+            return None;
+        }
+        let interval = now.saturating_sub(meter.last_reading_at);
+        if interval < self.min_interval_secs {
+            return Some(AnomalyKind::TooFrequent);
+        }
+
+        let delta = reading.saturating_sub(meter.last_reading) as u128;
+        if self.max_flow_lph > 0
+            && delta * 3_600 > self.max_flow_lph as u128 * interval.max(0) as u128
+        {
+            return Some(AnomalyKind::FlowTooHigh);
+        }
+
+        if self.max_z_score_x100 > 0 && meter.flow_samples >= self.min_samples.max(1) {
+            let rate = meter.flow_rate_lpd(reading, now)?;
+            let diff = rate.abs_diff(meter.flow_mean_lpd) as u128;
+            // |x - mean| / sd > z  <=>  diff² · 100² > z_x100² · var
+            let lhs = diff.saturating_mul(diff).saturating_mul(10_000);
+            let z = self.max_z_score_x100 as u128;
+            if lhs > (z * z).saturating_mul(meter.flow_var) {
+                return Some(AnomalyKind::ZScore);
+            }
+        }
+        None
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PlausibilityParams {
+    pub min_interval_secs: i64,
+    pub max_flow_lph: u64,
+    pub max_z_score_x100: u32,
+    pub min_samples: u32,
+    pub ewma_weight_bps: u16,
+    pub action: AnomalyAction,
+}
+
+// One owner's rewards from one meter, waiting to be settled and claimed
+#[account]
+#[derive(InitSpace)]
+pub struct RewardLedger {
+    pub meter: Pubkey,
+    pub owner: Pubkey,
+    pub pending: u64,   // inside the settlement window, can still be clawed back
+    pub claimable: u64, // settled, mintable by the owner
+    pub claimable_liters: u64,
+    pub disputed: u64,  // frozen until an auditor resolves the dispute
+    pub total_claimed: u64,
+    pub total_clawed_back: u64,
+    pub claim_epoch: u64, // number of claims so far
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct MintConfig {
+    pub residential_threshold: u64,
+    pub municipal_multiplier: u8, // percent weight on NRW reduction credits
+    pub jal_per_m3_bps: u32, // JAL per m³ in bps; 10_000 = the 1 JAL = 1 m³ peg
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey, // default = no transfer in flight
+    pub mint: Pubkey,
+    // M-of-N oracle signatures required per MeterType (indexed by discriminant)
+    pub oracle_quorum: [u8; 3],
+    pub max_clock_drift_secs: i64,
+    pub settlement_window_secs: i64, // accrued rewards wait this long before they're claimable
+    pub require_device_signature: bool, // reject readings from meters without a device key
+    pub paused: bool,
+    pub paused_meter_types: u8, // bitmask of MeterType::bit()
+    pub bump: u8,
+    pub mint_auth_bump: u8,
+}
+
+impl MintConfig {
+    // Every reading/mint path goes through this before touching balances
+    pub fn check_not_paused(&self, meter_type: MeterType) -> Result<()> {
+        require!(!self.paused, CustomError::ProgramPaused);
+        require!(
+            self.paused_meter_types & meter_type.bit() == 0,
+            CustomError::MeterTypePaused
+        );
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tokenomics {
+    pub total_cap: u64,
+    pub buckets: [BucketState; 5], // indexed by AllocationBucket
+    pub bump: u8,
+}
+
+impl Tokenomics {
+    // Books `amount` against a bucket, refusing anything past its allocation
+    pub fn charge(&mut self, bucket: AllocationBucket, amount: u64) -> Result<()> {
+        let state = &mut self.buckets[bucket as usize];
+        let minted = state
+            .minted
+            .checked_add(amount)
+            .ok_or(CustomError::BucketCapExceeded)?;
+        require!(minted <= state.allocation, CustomError::BucketCapExceeded);
+        require!(
+            self.total_minted().saturating_add(amount) <= self.total_cap,
+            CustomError::SupplyCapExceeded
+        );
+
+        self.buckets[bucket as usize].minted = minted;
+        Ok(())
+    }
+
+    // Returns headroom for tokens that were minted but never circulated
+    pub fn release(&mut self, bucket: AllocationBucket, amount: u64) {
+        let state = &mut self.buckets[bucket as usize];
+        state.minted = state.minted.saturating_sub(amount);
+    }
+
+    pub fn remaining(&self, bucket: AllocationBucket) -> u64 {
+        let state = &self.buckets[bucket as usize];
+        state.allocation.saturating_sub(state.minted)
+    }
+
+    pub fn total_minted(&self) -> u64 {
+        self.buckets.iter().map(|b| b.minted).sum()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct BucketState {
+    pub allocation: u64,
+    pub minted: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum AllocationBucket { CommunityRewards, Reserve, Team, Ecosystem, Marketing }
+
+impl AllocationBucket {
+    pub const ALL: [AllocationBucket; 5] = [
+        AllocationBucket::CommunityRewards,
+        AllocationBucket::Reserve,
+        AllocationBucket::Team,
+        AllocationBucket::Ecosystem,
+        AllocationBucket::Marketing,
+    ];
+
+    // Whitepaper split: 50 / 20 / 15 / 10 / 5
+    pub fn share_bps(self) -> u64 {
+        match self {
+            AllocationBucket::CommunityRewards => 5_000,
+            AllocationBucket::Reserve => 2_000,
+            AllocationBucket::Team => 1_500,
+            AllocationBucket::Ecosystem => 1_000,
+            AllocationBucket::Marketing => 500,
+        }
+    }
+
+    // Per-bucket allocations summing exactly to `total_cap`
+    pub fn split(total_cap: u64) -> [BucketState; 5] {
+        let mut buckets = [BucketState::default(); 5];
+        let mut assigned = 0u64;
+        for bucket in Self::ALL {
+            let allocation = (total_cap as u128 * bucket.share_bps() as u128
+                / BPS_DENOMINATOR as u128) as u64;
+            buckets[bucket as usize].allocation = allocation;
+            assigned += allocation;
+        }
+        // Rounding dust goes to the community
+        buckets[AllocationBucket::CommunityRewards as usize].allocation += total_cap - assigned;
+        buckets
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingSchedule {
+    pub beneficiary: Pubkey,
+    pub schedule_id: u64,
+    pub vault: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_ts: i64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64, // linear release after the cliff
+    pub period_secs: i64,   // release granularity
+    pub revocable: bool,
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    // Nothing before the cliff, then whole periods released linearly over `duration_secs`
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if self.revoked {
+            return self.total_amount;
+        }
+        let release_start = self.start_ts.saturating_add(self.cliff_secs);
+        if now < release_start {
+            return 0;
+        }
+
+        let elapsed = now - release_start;
+        let unlocked_secs = (elapsed / self.period_secs * self.period_secs).min(self.duration_secs);
+        (self.total_amount as u128 * unlocked_secs as u128 / self.duration_secs as u128) as u64
+    }
+
+    pub fn releasable(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct VestingParams {
+    pub total_amount: u64,
+    pub start_ts: i64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64,
+    pub period_secs: i64,
+    pub revocable: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RetireeAccount {
+    pub retiree: Pubkey,
+    pub total_retired: u64,
+    pub retirement_count: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Retirement {
+    pub retiree: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub liters: u64, // behind the drawn batches; 0 if none were drawn
+    #[max_len(64)]
+    pub beneficiary_name: String,
+    pub beneficiary_hash: [u8; 32],
+    pub vintage_start: i64,
+    pub vintage_end: i64,
+    #[max_len(8)]
+    pub sources: Vec<Pubkey>, // originating meters / projects
+    #[max_len(8)]
+    pub batches: Vec<BatchDraw>,
+    #[max_len(128)]
+    pub reason: String,
+    pub retired_at: i64,
+    pub bump: u8,
+}
+
+// Metadata record behind a soulbound certificate token
+#[account]
+#[derive(InitSpace)]
+pub struct RetirementCertificate {
+    pub retirement: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,    // JAL base units retired
+    pub amount_m3: u64, // whole m³ behind the retirement's drawn batches
+    pub vintage_start: i64,
+    pub vintage_end: i64,
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RetireParams {
+    pub amount: u64,
+    pub beneficiary_name: String,
+    pub beneficiary_hash: [u8; 32],
+    pub vintage_start: i64,
+    pub vintage_end: i64,
+    pub sources: Vec<Pubkey>,
+    pub batches: Vec<BatchDraw>, // batch accounts follow as remaining accounts, same order
+    pub reason: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct BatchDraw {
+    pub batch: Pubkey,
+    pub amount: u64,
+}
+
+// A treatment plant: raw sewage in through one meter, treated water out through another
+#[account]
+#[derive(InitSpace)]
+pub struct Facility {
+    #[max_len(32)]
+    pub facility_id: String,
+    pub operator: Pubkey,
+    pub beneficiary: Pubkey,
+    pub inflow_meter: Pubkey,
+    pub outflow_meter: Pubkey,
+    pub region: u16,
+    pub last_inflow_reading: u64,  // snapshot at last claim
+    pub last_outflow_reading: u64, // snapshot at last claim
+    pub period_secs: i64,
+    pub max_liters_per_period: u64,
+    pub open_period: ReclamationPeriod,           // where new volume is attributed
+    pub closed_period: Option<ReclamationPeriod>, // ended, awaiting its quality report
+    pub total_recycled_liters: u64,
+    pub issuance_remainder: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct ReclamationPeriod {
+    pub period: u64,
+    pub recycled_liters: u64, // attributed, not yet credited or forfeited
+    pub credited_liters: u64, // minted so far, against `max_liters_per_period`
+}
+
+impl Facility {
+    pub fn period_index(&self, now: i64) -> u64 {
+        (now.max(0) / self.period_secs) as u64
+    }
+
+    pub fn period_start(&self, period: u64) -> i64 {
+        (period as i64).saturating_mul(self.period_secs)
+    }
+
+    // Adds volume to the reading's period, closing the open one when the readings move
+    // past it. Only one closed period waits for its report; an older one still waiting
+    // is returned as forfeited.
+    pub fn attribute(&mut self, reading_period: u64, liters: u64) -> Option<ReclamationPeriod> {
+        let mut stale = None;
+        if reading_period > self.open_period.period {
+            stale = self.closed_period.replace(self.open_period);
+            self.open_period = ReclamationPeriod {
+                period: reading_period,
+                ..Default::default()
+            };
+        }
+        self.open_period.recycled_liters += liters;
+        stale.filter(|stale| stale.recycled_liters > 0)
+    }
+}
+
+// A district metered area: one bulk inflow meter feeding a set of customer meters
+#[account]
+#[derive(InitSpace)]
+pub struct District {
+    #[max_len(32)]
+    pub district_id: String,
+    pub utility: Pubkey,
+    pub beneficiary: Pubkey,
+    pub inflow_meter: Pubkey,
+    pub region: u16,
+    pub baseline_loss_bps: u16, // registered non-revenue water rate
+    pub period_secs: i64,
+    pub period_start_at: i64,
+    pub period_count: u64,
+    pub last_inflow_reading: u64, // snapshot at last close
+    #[max_len(32)]
+    pub customers: Vec<DistrictCustomer>,
+    pub total_reduction_liters: u64,
+    pub issuance_remainder: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct DistrictCustomer {
+    pub meter: Pubkey,
+    pub snapshot: u64, // reading at last close
+}
+
+// A building's residents' association; member meters carry its key in `meter.building`
+#[account]
+#[derive(InitSpace)]
+pub struct Association {
+    pub building: Pubkey,
+    #[max_len(64)]
+    pub name: String,
+    pub manager: Pubkey,
+    pub treasury: Pubkey,
+    pub credit_per_jal: u64, // local-currency minor units per whole JAL
+    pub total_redeemed: u64,
+    pub total_credit_issued: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl Association {
+    // Local-currency minor units for `amount` JAL base units
+    pub fn credit_for(&self, amount: u64, decimals: u8) -> u64 {
+        (amount as u128 * self.credit_per_jal as u128 / 10u128.pow(decimals as u32)) as u64
+    }
+}
+
+// Per-building "top saver" competition, paid from the ecosystem bucket
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub association: Pubkey,
+    pub top_n: u8,
+    pub bonus_pool: u64, // per period, split evenly across the top N
+    pub streak_bonus_bps: u16, // extra per consecutive saving period
+    pub max_streak_bonus_periods: u16,
+    pub period_secs: i64,
+    pub period_start_at: i64,
+    pub current_period: u64,
+    pub period_savers: u32, // tallies that saved in `current_period`
+    #[max_len(MAX_LEADERBOARD_TOP_N)]
+    pub standings: Vec<LeaderboardEntry>, // best savers this period, highest first
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LeaderboardEntry {
+    pub tally: Pubkey,
+    pub meter: Pubkey,
+    pub beneficiary: Pubkey,
+    pub meter_type: MeterType,
+    pub saved_liters: u64,
+    pub streak: u16,
+}
+
+impl Leaderboard {
+    // Tallies only grow within a period, so one that drops off the standings can
+    // only come back with a bigger total, which is re-ranked here when it lands.
+    // Ties keep whoever reached the total first ahead.
+    pub fn rank(&mut self, entry: LeaderboardEntry) {
+        if let Some(existing) = self.standings.iter_mut().find(|e| e.tally == entry.tally) {
+            *existing = entry;
+        } else if self.standings.len() < MAX_LEADERBOARD_TOP_N as usize {
+            self.standings.push(entry);
+        } else if let Some(last) = self.standings.last_mut() {
+            if entry.saved_liters <= last.saved_liters {
+                return;
+            }
+            *last = entry;
+        }
+        self.standings.sort_by_key(|e| std::cmp::Reverse(e.saved_liters));
+    }
+
+    // Lowers a standing after a forfeit. A saver outside the standings that now
+    // out-saves it only moves in on their next settled reward.
+    pub fn lower(&mut self, tally: Pubkey, saved_liters: u64) {
+        if saved_liters == 0 {
+            self.standings.retain(|e| e.tally != tally);
+        } else if let Some(entry) = self.standings.iter_mut().find(|e| e.tally == tally) {
+            entry.saved_liters = saved_liters;
+        }
+        self.standings.sort_by_key(|e| std::cmp::Reverse(e.saved_liters));
+    }
+
+    pub fn bonus_for(&self, share: u64, streak: u16) -> u64 {
+        let bonus_periods = streak.saturating_sub(1).min(self.max_streak_bonus_periods) as u64;
+        let multiplier_bps = BPS_DENOMINATOR + bonus_periods * self.streak_bonus_bps as u64;
+        (share as u128 * multiplier_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeaderboardParams {
+    pub top_n: u8,
+    pub bonus_pool: u64,
+    pub streak_bonus_bps: u16,
+    pub max_streak_bonus_periods: u16,
+    pub period_secs: i64,
+}
+
+// One meter's savings in the current leaderboard period
+#[account]
+#[derive(InitSpace)]
+pub struct SavingsTally {
+    pub association: Pubkey,
+    pub meter: Pubkey,
+    pub beneficiary: Pubkey, // refreshed from the meter on every record
+    pub period: u64,
+    pub saved_liters: u64,
+    pub streak: u16, // consecutive saving periods ending at `period`
+    pub bump: u8,
+}
+
+impl SavingsTally {
+    // Returns true the first time the meter saves in `period`
+    pub fn record(&mut self, period: u64, liters: u64, beneficiary: Pubkey) -> bool {
+        self.beneficiary = beneficiary;
+        // A streak means the meter has already saved in `self.period`, even if forfeited since
+        if self.streak > 0 && self.period == period {
+            self.saved_liters += liters;
+            return false;
+        }
+        let continued = self.saved_liters > 0 && self.period + 1 == period;
+        self.streak = if continued { self.streak + 1 } else { 1 };
+        self.period = period;
+        self.saved_liters = liters;
+        true
+    }
+
+    // Takes back clawed-back savings; false once `period` has closed and been paid
+    pub fn forfeit(&mut self, period: u64, liters: u64) -> bool {
+        if self.period != period {
+            return false;
+        }
+        self.saved_liters = self.saved_liters.saturating_sub(liters);
+        true
+    }
+}
+
+// A resident's maintenance-fee credit with their association, read by its billing system
+#[account]
+#[derive(InitSpace)]
+pub struct FeeCredit {
+    pub association: Pubkey,
+    pub resident: Pubkey,
+    pub balance: u64, // not yet applied to a bill
+    pub lifetime_credit: u64,
+    pub lifetime_jal: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl FeeCredit {
+    pub fn add(
+        &mut self,
+        association: Pubkey,
+        resident: Pubkey,
+        amount: u64,
+        credit: u64,
+        now: i64,
+        bump: u8,
+    ) {
+        self.association = association;
+        self.resident = resident;
+        self.balance += credit;
+        self.lifetime_credit += credit;
+        self.lifetime_jal += amount;
+        self.updated_at = now;
+        self.bump = bump;
+    }
+}
+
+// A resident's standing order: the association may pull a fixed JAL amount each
+// billing period, via an SPL delegate approval granted to this PDA
+#[account]
+#[derive(InitSpace)]
+pub struct FeeMandate {
+    pub association: Pubkey,
+    pub resident: Pubkey,
+    pub source: Pubkey,
+    pub amount_per_period: u64,
+    pub period_secs: i64,
+    pub created_at: i64, // distinguishes receipts of a re-created mandate at the same PDA
+    pub next_due_at: i64,
+    pub debit_count: u64,
+    pub total_debited: u64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+// Proof of one executed mandate debit
+#[account]
+#[derive(InitSpace)]
+pub struct FeeReceipt {
+    pub mandate: Pubkey,
+    pub association: Pubkey,
+    pub resident: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub credit: u64,
+    pub executed_at: i64,
+    pub bump: u8,
+}
+
+// Treated-effluent limits for one region; pH in hundredths, turbidity in hundredths of NTU
+#[account]
+#[derive(InitSpace)]
+pub struct QualityStandard {
+    pub region: u16,
+    pub ph_min_x100: u16,
+    pub ph_max_x100: u16,
+    pub max_tds_mg_l: u32,
+    pub max_bod_mg_l: u32,
+    pub max_cod_mg_l: u32,
+    pub max_turbidity_ntu_x100: u32,
+    pub bump: u8,
+}
+
+impl QualityStandard {
+    pub fn apply(&mut self, params: QualityParams) -> Result<()> {
+        require!(
+            params.ph_min_x100 <= params.ph_max_x100 && params.ph_max_x100 <= 1_400,
+            CustomError::InvalidQualityStandard
+        );
+
+        self.ph_min_x100 = params.ph_min_x100;
+        self.ph_max_x100 = params.ph_max_x100;
+        self.max_tds_mg_l = params.max_tds_mg_l;
+        self.max_bod_mg_l = params.max_bod_mg_l;
+        self.max_cod_mg_l = params.max_cod_mg_l;
+        self.max_turbidity_ntu_x100 = params.max_turbidity_ntu_x100;
+        Ok(())
+    }
+
+    pub fn passes(&self, m: &QualityMeasurements) -> bool {
+        (self.ph_min_x100..=self.ph_max_x100).contains(&m.ph_x100)
+            && m.tds_mg_l <= self.max_tds_mg_l
+            && m.bod_mg_l <= self.max_bod_mg_l
+            && m.cod_mg_l <= self.max_cod_mg_l
+            && m.turbidity_ntu_x100 <= self.max_turbidity_ntu_x100
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct QualityParams {
+    pub ph_min_x100: u16,
+    pub ph_max_x100: u16,
+    pub max_tds_mg_l: u32,
+    pub max_bod_mg_l: u32,
+    pub max_cod_mg_l: u32,
+    pub max_turbidity_ntu_x100: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct QualityMeasurements {
+    pub ph_x100: u16,
+    pub tds_mg_l: u32,
+    pub bod_mg_l: u32,
+    pub cod_mg_l: u32,
+    pub turbidity_ntu_x100: u32,
+}
+
+// One lab report per facility per period, judged against the region's standard when filed
+#[account]
+#[derive(InitSpace)]
+pub struct QualityReport {
+    pub facility: Pubkey,
+    pub period: u64,
+    pub measurements: QualityMeasurements,
+    pub passed: bool,
+    pub reported_by: Pubkey,
+    pub reported_at: i64,
+    pub bump: u8,
+}
+
+// All JAL minted for one (meter type, region, vintage year)
+#[account]
+#[derive(InitSpace)]
+pub struct IssuanceBatch {
+    pub meter_type: MeterType,
+    pub region: u16,
+    pub vintage_year: u16,
+    pub liters: u64,
+    pub issued: u64,
+    pub retired: u64,
+    pub mint_count: u64,
+    pub bump: u8,
+}
+
+impl IssuanceBatch {
+    pub fn book(
+        &mut self,
+        meter_type: MeterType,
+        region: u16,
+        vintage_year: u16,
+        liters: u64,
+        amount: u64,
+        bump: u8,
+    ) {
+        self.meter_type = meter_type;
+        self.region = region;
+        self.vintage_year = vintage_year;
+        self.liters += liters;
+        self.issued += amount;
+        self.mint_count += 1;
+        self.bump = bump;
+    }
+
+    // Reverses a clawed-back issuance; volume already retired can't be unbooked
+    pub fn unbook(&mut self, liters: u64, amount: u64) -> Result<()> {
+        require!(amount <= self.unretired(), CustomError::BatchOverRetired);
+        self.liters -= liters;
+        self.issued -= amount;
+        Ok(())
+    }
+
+    pub fn unretired(&self) -> u64 {
+        self.issued.saturating_sub(self.retired)
+    }
+
+    // Returns the liters behind `amount`, so successive draws add up to the batch's liters
+    pub fn retire(&mut self, amount: u64) -> Result<u64> {
+        require!(amount <= self.unretired(), CustomError::BatchOverRetired);
+        let before = self.unretired_liters();
+        self.retired += amount;
+        Ok(before - self.unretired_liters())
+    }
+
+    // Retirements are in JAL, so the liters behind them are taken pro rata
+    pub fn unretired_liters(&self) -> u64 {
+        if self.issued == 0 {
+            return 0;
+        }
+        (self.liters as u128 * self.unretired() as u128 / self.issued as u128) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BatchBalance {
+    pub amount: u64,
+    pub liters: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Oracle {
+    pub key: Pubkey,
+    pub meter_types: u8, // bitmask of MeterType::bit()
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Role { ConfigManager, Reporter, Pauser, MeterRegistrar, Auditor }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum MeterType { Residential, Industrial, Municipal }
+
+impl MeterType {
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MeterStatus { Active, Inactive, Replaced }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RewardStatus { None, Pending, Settled, ClawedBack }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ReadingStatus { Valid, Disputed, Invalid, Quarantined }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum AnomalyAction { Reject, Quarantine }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum AnomalyKind { TooFrequent, FlowTooHigh, ZScore }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum VolumeUnit { Liters, CubicMeters, Gallons }
+
+impl VolumeUnit {
+    // Gallons round down to the liter; readings are cumulative so the error never compounds
+    pub fn to_liters(self, value: u64) -> Result<u64> {
+        let liters = match self {
+            VolumeUnit::Liters => Some(value),
+            VolumeUnit::CubicMeters => value.checked_mul(LITERS_PER_M3),
+            VolumeUnit::Gallons => {
+                u64::try_from(value as u128 * NANOLITERS_PER_GALLON / 1_000_000_000).ok()
+            }
+        };
+        liters.ok_or_else(|| error!(CustomError::Overflow))
+    }
+}
+
+// === EVENTS ===
+#[event]
+pub struct Initialized {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub freeze_authority: Option<Pubkey>,
+    pub residential_threshold: u64,
+    pub municipal_multiplier: u8,
+    pub jal_per_m3_bps: u32,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub old_residential_threshold: u64,
+    pub residential_threshold: u64,
+    pub old_municipal_multiplier: u8,
+    pub municipal_multiplier: u8,
+    pub old_jal_per_m3_bps: u32,
+    pub jal_per_m3_bps: u32,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct BaselineConfigUpdated {
+    pub params: BaselineParams,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct ClockDriftSet {
+    pub old_max_clock_drift_secs: i64,
+    pub max_clock_drift_secs: i64,
+}
+
+#[event]
+pub struct PlausibilityRulesSet {
+    pub meter_type: MeterType,
+    pub params: PlausibilityParams,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct DeviceSignatureRequirementSet {
+    pub old_required: bool,
+    pub required: bool,
+}
+
+#[event]
+pub struct SettlementWindowSet {
+    pub old_settlement_window_secs: i64,
+    pub settlement_window_secs: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub proposed: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub previous: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct MeterRegistered {
+    pub meter: Pubkey,
+    pub meter_id: String,
+    pub meter_type: MeterType,
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub building: Pubkey,
+    pub region: u16,
+    pub install_date: i64,
+}
+
+#[event]
+pub struct MeterDeactivated {
+    pub meter: Pubkey,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterRegisterSet {
+    pub meter: Pubkey,
+    pub old_register_digits: u8,
+    pub register_digits: u8,
+}
+
+#[event]
+pub struct MeterReplaced {
+    pub old_meter: Pubkey,
+    pub new_meter: Pubkey,
+    pub final_reading: u64,
+    pub start_register: u64,
+    pub unit: VolumeUnit,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct MeterRelinked {
+    pub record: Pubkey,
+    pub old_meter: Pubkey,
+    pub new_meter: Pubkey,
+}
+
+#[event]
+pub struct MeterTransferred {
+    pub meter: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+}
+
+#[event]
+pub struct MeterDeviceSet {
+    pub meter: Pubkey,
+    pub device_key: Pubkey,
+}
+
+#[event]
+pub struct MeterBaselineSet {
+    pub meter: Pubkey,
+    pub old_baseline_lpd: u64,
+    pub baseline_lpd: u64,
+}
+
+#[event]
+pub struct OracleRegistered {
+    pub oracle: Pubkey,
+    pub meter_types: u8,
+}
+
+#[event]
+pub struct OracleRemoved {
+    pub oracle: Pubkey,
+}
+
+#[event]
+pub struct OracleQuorumSet {
+    pub meter_type: MeterType,
+    pub old_quorum: u8,
+    pub quorum: u8,
+}
+
+#[event]
+pub struct TokenomicsInitialized {
+    pub total_cap: u64,
+    pub allocations: [u64; 5],
+}
+
+#[event]
+pub struct AllocationMinted {
+    pub bucket: AllocationBucket,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct VestingScheduleCreated {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub schedule_id: u64,
+    pub params: VestingParams,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+}
+
+#[event]
+pub struct VestingRevoked {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub vested: u64,
+    pub unvested: u64,
+}
+
+#[event]
+pub struct JalRetired {
+    pub retirement: Pubkey,
+    pub retiree: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub beneficiary_hash: [u8; 32],
+    pub vintage_start: i64,
+    pub vintage_end: i64,
+    pub total_retired: u64,
+}
+
+#[event]
+pub struct CertificateIssued {
+    pub certificate: Pubkey,
+    pub retirement: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount_m3: u64,
+}
+
+#[event]
+pub struct FacilityRegistered {
+    pub facility: Pubkey,
+    pub facility_id: String,
+    pub inflow_meter: Pubkey,
+    pub outflow_meter: Pubkey,
+    pub beneficiary: Pubkey,
+    pub max_liters_per_period: u64,
+}
+
+#[event]
+pub struct QualityStandardSet {
+    pub region: u16,
+    pub params: QualityParams,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct QualityReported {
+    pub facility: Pubkey,
+    pub period: u64,
+    pub measurements: QualityMeasurements,
+    pub passed: bool,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct ReclamationForfeited {
+    pub facility: Pubkey,
+    pub period: u64,
+    pub recycled_liters: u64,
+}
+
+#[event]
+pub struct ReclamationMinted {
+    pub facility: Pubkey,
+    pub period: u64,
+    pub recycled_liters: u64,
+    pub credited_liters: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DistrictRegistered {
+    pub district: Pubkey,
+    pub district_id: String,
+    pub inflow_meter: Pubkey,
+    pub beneficiary: Pubkey,
+    pub baseline_loss_bps: u16,
+}
+
+#[event]
+pub struct DistrictMeterSet {
+    pub district: Pubkey,
+    pub meter: Pubkey,
+    pub added: bool,
+}
+
+#[event]
+pub struct DistrictPeriodClosed {
+    pub district: Pubkey,
+    pub period: u64,
+    pub supplied_liters: u64,
+    pub billed_liters: u64,
+    pub loss_bps: u16,
+    pub baseline_loss_bps: u16,
+    pub credited_liters: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AssociationCreated {
+    pub association: Pubkey,
+    pub building: Pubkey,
+    pub name: String,
+    pub manager: Pubkey,
+    pub treasury: Pubkey,
+    pub credit_per_jal: u64,
+}
+
+#[event]
+pub struct AssociationTreasuryWithdrawn {
+    pub association: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct AssociationRateSet {
+    pub association: Pubkey,
+    pub old_credit_per_jal: u64,
+    pub credit_per_jal: u64,
+}
+
+#[event]
+pub struct FeeDiscountRedeemed {
+    pub association: Pubkey,
+    pub resident: Pubkey,
+    pub meter: Pubkey,
+    pub amount: u64,
+    pub credit: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct FeeCreditApplied {
+    pub association: Pubkey,
+    pub resident: Pubkey,
+    pub credit: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct FeeMandateCreated {
+    pub mandate: Pubkey,
+    pub association: Pubkey,
+    pub resident: Pubkey,
+    pub amount_per_period: u64,
+    pub period_secs: i64,
+    pub total_allowance: u64,
+}
+
+#[event]
+pub struct FeeDebited {
+    pub mandate: Pubkey,
+    pub receipt: Pubkey,
+    pub resident: Pubkey,
+    pub amount: u64,
+    pub credit: u64,
+    pub next_due_at: i64,
+}
+
+#[event]
+pub struct FeeMandatePauseSet {
+    pub mandate: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct FeeMandateCancelled {
+    pub mandate: Pubkey,
+    pub resident: Pubkey,
+    pub debit_count: u64,
+    pub total_debited: u64,
+}
+
+#[event]
+pub struct LeaderboardConfigured {
+    pub association: Pubkey,
+    pub params: LeaderboardParams,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct LeaderboardJoined {
+    pub association: Pubkey,
+    pub meter: Pubkey,
+}
+
+#[event]
+pub struct LeaderboardBonusPaid {
+    pub association: Pubkey,
+    pub period: u64,
+    pub rank: u8,
+    pub meter: Pubkey,
+    pub saved_liters: u64,
+    pub streak: u16,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LeaderboardPeriodClosed {
+    pub association: Pubkey,
+    pub period: u64,
+    pub winners: u8,
+}
+
+#[event]
+pub struct ReadingRecorded {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub sequence: u64,
+    pub value: u64,
+    pub previous: u64,
+    pub meter_type: MeterType,
+    pub device_signed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReadingQuarantined {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub kind: AnomalyKind,
+}
+
+#[event]
+pub struct RewardAccrued {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub liters_saved: u64,
+    pub amount: u64,
+    pub meter_type: MeterType,
+    pub settles_at: i64,
+}
+
+#[event]
+pub struct RewardSettled {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub amount: u64,
+    pub claimable: u64,
+}
+
+#[event]
+pub struct JalMinted {
+    pub meter: Pubkey,
+    pub beneficiary: Pubkey,
+    pub liters_saved: u64,
+    pub amount: u64,
+    pub meter_type: MeterType,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReadingDisputed {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub reason_code: u16,
+    pub amount: u64,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct QuarantineResolved {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub valid: bool,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub valid: bool,
+    pub amount: u64,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct RewardClawedBack {
+    pub meter: Pubkey,
+    pub reading: Pubkey,
+    pub amount: u64,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct GlobalPauseSet {
+    pub paused: bool,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterTypePauseSet {
+    pub meter_type: MeterType,
+    pub paused: bool,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MeterFrozenSet {
+    pub meter: Pubkey,
+    pub frozen: bool,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum CustomError {
+    #[msg("Invalid meter ID")]
+    InvalidMeterId,
+    #[msg("Reading must be > 0")]
+    InvalidReading,
+    #[msg("Only the super-admin can call this")]
+    Unauthorized,
+    #[msg("Install date cannot be in the future")]
+    InvalidInstallDate,
+    #[msg("Meter is not active")]
+    MeterInactive,
+    #[msg("Reading is below the meter's last recorded value")]
+    ReadingBelowPrevious,
+    #[msg("Invalid baseline configuration")]
+    InvalidBaselineConfig,
+    #[msg("Invalid oracle account")]
+    InvalidOracle,
+    #[msg("Malformed Ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("Not enough oracle attestations for this reading")]
+    OracleQuorumNotMet,
+    #[msg("Payload nonce must be strictly increasing")]
+    StaleNonce,
+    #[msg("Device timestamp too far from chain clock")]
+    DeviceClockDrift,
+    #[msg("Payload is not signed by the meter's device key")]
+    DeviceSignatureMissing,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("This meter type is paused")]
+    MeterTypePaused,
+    #[msg("Meter is frozen")]
+    MeterFrozen,
+    #[msg("Mint would exceed the total supply cap")]
+    SupplyCapExceeded,
+    #[msg("Mint would exceed the allocation bucket")]
+    BucketCapExceeded,
+    #[msg("This bucket cannot be minted directly")]
+    BucketNotManuallyMintable,
+    #[msg("Amount must be > 0")]
+    InvalidAmount,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Nothing to claim yet")]
+    NothingToClaim,
+    #[msg("Vesting schedule is not revocable")]
+    VestingNotRevocable,
+    #[msg("Invalid retirement details")]
+    InvalidRetirement,
+    #[msg("Retirement exceeds the batch's unretired volume")]
+    BatchOverRetired,
+    #[msg("Only a retirement drawn against vintage batches can be certified")]
+    RetirementUnbacked,
+    #[msg("Settled reward's vintage batch account is missing")]
+    InvalidBatch,
+    #[msg("Invalid facility configuration")]
+    InvalidFacility,
+    #[msg("No passing quality report for the current period")]
+    QualityNotAttested,
+    #[msg("Invalid water-quality standard")]
+    InvalidQualityStandard,
+    #[msg("Quality report period is in the future")]
+    InvalidQualityReport,
+    #[msg("Invalid district configuration or customer meters")]
+    InvalidDistrict,
+    #[msg("District period has not ended yet")]
+    DistrictPeriodOpen,
+    #[msg("Invalid association configuration")]
+    InvalidAssociation,
+    #[msg("Signer does not own a meter in this building")]
+    NotAResident,
+    #[msg("Invalid fee mandate terms")]
+    InvalidMandate,
+    #[msg("Fee mandate is paused")]
+    MandatePaused,
+    #[msg("Fee mandate is not due yet")]
+    MandateNotDue,
+    #[msg("Source token account already has a delegate")]
+    SourceAlreadyDelegated,
+    #[msg("Invalid leaderboard configuration or accounts")]
+    InvalidLeaderboard,
+    #[msg("Leaderboard period has not ended yet")]
+    LeaderboardPeriodOpen,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Reading has no pending reward")]
+    RewardNotPending,
+    #[msg("Reward has already been claimed")]
+    RewardAlreadyClaimed,
+    #[msg("Reward is still inside its settlement window")]
+    SettlementWindowOpen,
+    #[msg("Reading is disputed or already voided")]
+    ReadingNotValid,
+    #[msg("Reading is not under dispute")]
+    ReadingNotDisputed,
+    #[msg("Reading is not quarantined")]
+    ReadingNotQuarantined,
+    #[msg("Reading's challenge period is over")]
+    ChallengePeriodOver,
+    #[msg("Invalid plausibility rules")]
+    InvalidPlausibilityRules,
+    #[msg("Reading failed a plausibility check")]
+    ImplausibleReading,
+    #[msg("Invalid meter register configuration")]
+    InvalidRegister,
+    #[msg("Meter has not been replaced")]
+    MeterNotReplaced,
+    #[msg("Record does not reference the replaced meter")]
+    MeterNotLinked,
+    #[msg("Record the old meter's final register through record_reading first")]
+    FinalReadingNotRecorded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every field zero, empty or its first variant
+    fn zeroed<T: AnchorDeserialize>() -> T {
+        T::deserialize(&mut &[0u8; 4096][..]).unwrap()
+    }
+
+    // A meter whose last reading (and open period) started at `reading`, `at`
+    fn meter_at(reading: u64, at: i64) -> Meter {
+        let mut meter: Meter = zeroed();
+        meter.reading_count = 1;
+        meter.last_reading = reading;
+        meter.last_reading_at = at;
+        meter.period_start_reading = reading;
+        meter.period_start_at = at;
+        meter.baseline_updated_at = at;
+        meter
+    }
+
+    fn baseline() -> BaselineConfig {
+        BaselineConfig {
+            default_baseline_lpd: 100,
+            min_period_secs: SECONDS_PER_DAY,
+            max_period_secs: 30 * SECONDS_PER_DAY,
+            rebaseline_interval_secs: SECONDS_PER_DAY,
+            rebaseline_weight_bps: 5_000,
+            allow_upward_rebaseline: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn period_savings_waits_for_a_first_reading_and_the_minimum_period() {
+        let baseline = baseline();
+        let mut meter = meter_at(0, 0);
+        meter.reading_count = 0;
+        assert_eq!(meter.period_savings(&baseline, 0, 10 * SECONDS_PER_DAY), None);
+
+        let meter = meter_at(0, 0);
+        assert_eq!(meter.period_savings(&baseline, 0, SECONDS_PER_DAY - 1), None);
+        assert_eq!(meter.period_savings(&baseline, 0, SECONDS_PER_DAY), Some(100));
+    }
+
+    #[test]
+    fn period_savings_is_baseline_minus_consumption() {
+        let baseline = baseline();
+        let meter = meter_at(5_000, 0);
+        assert_eq!(meter.period_savings(&baseline, 5_600, 10 * SECONDS_PER_DAY), Some(400));
+        // Using more than the baseline saves nothing rather than going negative
+        assert_eq!(meter.period_savings(&baseline, 7_000, 10 * SECONDS_PER_DAY), Some(0));
+    }
+
+    #[test]
+    fn period_savings_caps_credited_time_at_the_maximum_period() {
+        let baseline = baseline();
+        let meter = meter_at(0, 0);
+        assert_eq!(meter.period_savings(&baseline, 0, 90 * SECONDS_PER_DAY), Some(3_000));
+    }
+
+    #[test]
+    fn close_period_blends_the_baseline_down_but_not_up() {
+        let baseline = baseline();
+
+        let mut meter = meter_at(0, 0);
+        meter.close_period(&baseline, 500, 10 * SECONDS_PER_DAY);
+        assert_eq!(meter.baseline_lpd, 75); // halfway between 100 and the observed 50
+        assert_eq!(meter.baseline_updated_at, 10 * SECONDS_PER_DAY);
+        assert_eq!(meter.period_start_reading, 500);
+        assert_eq!(meter.period_start_at, 10 * SECONDS_PER_DAY);
+
+        let mut meter = meter_at(0, 0);
+        meter.close_period(&baseline, 3_000, 10 * SECONDS_PER_DAY);
+        assert_eq!(meter.baseline_lpd, 0); // still on the default
+
+        let baseline = BaselineConfig { allow_upward_rebaseline: true, ..baseline };
+        let mut meter = meter_at(0, 0);
+        meter.close_period(&baseline, 3_000, 10 * SECONDS_PER_DAY);
+        assert_eq!(meter.baseline_lpd, 200);
+    }
+
+    #[test]
+    fn close_period_skips_rebaselining_until_due() {
+        let baseline = BaselineConfig {
+            rebaseline_interval_secs: 30 * SECONDS_PER_DAY,
+            ..baseline()
+        };
+        let mut meter = meter_at(0, 0);
+        meter.close_period(&baseline, 500, 10 * SECONDS_PER_DAY);
+        assert_eq!(meter.baseline_lpd, 0);
+        assert_eq!(meter.baseline_updated_at, 0);
+        assert_eq!(meter.period_start_reading, 500);
+    }
+
+    // Precompile data laid out like the web3.js helper: header, offsets, key, signature, message
+    fn ed25519_ix(entries: &[(Pubkey, &[u8], u16)]) -> Vec<u8> {
+        let mut offsets = Vec::new();
+        let mut body = Vec::new();
+        let body_start = 2 + entries.len() * 14;
+        for (key, message, ix_index) in entries {
+            let key_offset = (body_start + body.len()) as u16;
+            body.extend_from_slice(key.as_ref());
+            let sig_offset = (body_start + body.len()) as u16;
+            body.extend_from_slice(&[0u8; 64]);
+            let msg_offset = (body_start + body.len()) as u16;
+            body.extend_from_slice(message);
+            for field in [
+                sig_offset,
+                *ix_index,
+                key_offset,
+                *ix_index,
+                msg_offset,
+                message.len() as u16,
+                *ix_index,
+            ] {
+                offsets.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+        let mut data = vec![entries.len() as u8, 0];
+        data.extend(offsets);
+        data.extend(body);
+        data
+    }
+
+    #[test]
+    fn ed25519_signers_returns_keys_that_signed_the_message() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = ed25519_ix(&[
+            (alice, b"reading", u16::MAX),
+            (bob, b"something else", u16::MAX),
+            (alice, b"reading", u16::MAX),
+        ]);
+        let mut signers = Vec::new();
+        collect_ed25519_signers(&data, b"reading", &mut signers).unwrap();
+        assert_eq!(signers, vec![alice]);
+    }
+
+    #[test]
+    fn ed25519_signers_ignores_offsets_into_other_instructions() {
+        let data = ed25519_ix(&[(Pubkey::new_unique(), b"reading", 0)]);
+        let mut signers = Vec::new();
+        collect_ed25519_signers(&data, b"reading", &mut signers).unwrap();
+        assert!(signers.is_empty());
+    }
+
+    #[test]
+    fn ed25519_signers_rejects_truncated_data() {
+        let mut signers = Vec::new();
+        assert!(collect_ed25519_signers(&[], b"reading", &mut signers).is_err());
+
+        let data = ed25519_ix(&[(Pubkey::new_unique(), b"reading", u16::MAX)]);
+        assert!(collect_ed25519_signers(&data[..20], b"reading", &mut signers).is_err());
+        assert!(collect_ed25519_signers(&data[..data.len() - 1], b"reading", &mut signers).is_err());
+    }
+
+    #[test]
+    fn split_follows_the_whitepaper_and_sums_to_the_cap() {
+        let buckets = AllocationBucket::split(1_000_000);
+        let allocations: Vec<u64> = buckets.iter().map(|b| b.allocation).collect();
+        assert_eq!(allocations, vec![500_000, 200_000, 150_000, 100_000, 50_000]);
+    }
+
+    #[test]
+    fn split_gives_rounding_dust_to_the_community() {
+        let buckets = AllocationBucket::split(7);
+        let allocations: Vec<u64> = buckets.iter().map(|b| b.allocation).collect();
+        assert_eq!(allocations, vec![5, 1, 1, 0, 0]);
+
+        let cap = TOTAL_SUPPLY_TOKENS * 10u64.pow(9) + 3;
+        let total: u64 = AllocationBucket::split(cap).iter().map(|b| b.allocation).sum();
+        assert_eq!(total, cap);
+    }
+
+    fn schedule() -> VestingSchedule {
+        let mut schedule: VestingSchedule = zeroed();
+        schedule.total_amount = 1_200;
+        schedule.start_ts = 1_000;
+        schedule.cliff_secs = 100;
+        schedule.duration_secs = 1_200;
+        schedule.period_secs = 100;
+        schedule
+    }
+
+    #[test]
+    fn vested_amount_is_zero_until_the_cliff() {
+        let schedule = schedule();
+        assert_eq!(schedule.vested_amount(0), 0);
+        assert_eq!(schedule.vested_amount(1_099), 0);
+        assert_eq!(schedule.vested_amount(1_100), 0);
+    }
+
+    #[test]
+    fn vested_amount_releases_whole_periods_linearly() {
+        let schedule = schedule();
+        assert_eq!(schedule.vested_amount(1_199), 0);
+        assert_eq!(schedule.vested_amount(1_200), 100);
+        assert_eq!(schedule.vested_amount(1_750), 600);
+        assert_eq!(schedule.vested_amount(2_300), 1_200);
+        assert_eq!(schedule.vested_amount(i64::MAX), 1_200);
+    }
+
+    #[test]
+    fn vested_amount_of_a_revoked_schedule_is_its_remaining_total() {
+        let mut schedule = schedule();
+        schedule.total_amount = 300;
+        schedule.revoked = true;
+        assert_eq!(schedule.vested_amount(0), 300);
+
+        schedule.claimed_amount = 200;
+        assert_eq!(schedule.releasable(0), 100);
+    }
+
+    #[test]
+    fn retiring_a_batch_in_parts_draws_all_its_liters() {
+        let mut batch: IssuanceBatch = zeroed();
+        batch.liters = 1_000;
+        batch.issued = 3;
+
+        let drawn: u64 = (0..3).map(|_| batch.retire(1).unwrap()).sum();
+        assert_eq!(drawn, 1_000);
+        assert_eq!(batch.unretired(), 0);
+        assert!(batch.retire(1).is_err());
+    }
+
+    #[test]
+    fn vintage_year_follows_utc_calendar_years() {
+        assert_eq!(vintage_year(0), 1970);
+        assert_eq!(vintage_year(-1), 1969);
+        assert_eq!(vintage_year(1_704_067_199), 2023); // 2023-12-31T23:59:59Z
+        assert_eq!(vintage_year(1_704_067_200), 2024);
+        assert_eq!(vintage_year(1_709_164_800), 2024); // 2024-02-29
+        assert_eq!(vintage_year(1_735_689_599), 2024); // 2024-12-31T23:59:59Z
+        assert_eq!(vintage_year(4_102_444_800), 2100); // not a leap year
+    }
+
+    #[test]
+    fn unretired_liters_are_taken_pro_rata() {
+        let mut batch: IssuanceBatch = zeroed();
+        assert_eq!(batch.unretired_liters(), 0);
+
+        batch.liters = 3_000;
+        batch.issued = 3_000_000;
+        batch.retired = 1_000_000;
+        assert_eq!(batch.unretired(), 2_000_000);
+        assert_eq!(batch.unretired_liters(), 2_000);
+
+        batch.retired = batch.issued;
+        assert_eq!(batch.unretired_liters(), 0);
+    }
+
+    #[test]
+    fn tally_record_accumulates_a_period_and_tracks_streaks() {
+        let beneficiary = Pubkey::new_unique();
+        let mut tally: SavingsTally = zeroed();
+        assert!(tally.record(4, 100, beneficiary));
+        assert_eq!((tally.period, tally.saved_liters, tally.streak), (4, 100, 1));
+        assert_eq!(tally.beneficiary, beneficiary);
+
+        assert!(!tally.record(4, 50, beneficiary));
+        assert_eq!((tally.saved_liters, tally.streak), (150, 1));
+
+        assert!(tally.record(5, 10, beneficiary));
+        assert_eq!((tally.period, tally.saved_liters, tally.streak), (5, 10, 2));
+
+        // A period without savings breaks the streak
+        assert!(tally.record(7, 10, beneficiary));
+        assert_eq!(tally.streak, 1);
+    }
+
+    #[test]
+    fn bonus_for_adds_a_capped_streak_bonus() {
+        let mut leaderboard: Leaderboard = zeroed();
+        leaderboard.streak_bonus_bps = 1_000;
+        leaderboard.max_streak_bonus_periods = 3;
+        assert_eq!(leaderboard.bonus_for(1_000, 0), 1_000);
+        assert_eq!(leaderboard.bonus_for(1_000, 1), 1_000);
+        assert_eq!(leaderboard.bonus_for(1_000, 3), 1_200);
+        assert_eq!(leaderboard.bonus_for(1_000, 4), 1_300);
+        assert_eq!(leaderboard.bonus_for(1_000, 40), 1_300);
+    }
+
+    fn entry(tally: Pubkey, saved_liters: u64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            tally,
+            meter: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            meter_type: MeterType::Residential,
+            saved_liters,
+            streak: 1,
+        }
+    }
+
+    #[test]
+    fn rank_keeps_the_best_savers_highest_first() {
+        let mut leaderboard: Leaderboard = zeroed();
+        let tallies: Vec<Pubkey> =
+            (0..=MAX_LEADERBOARD_TOP_N).map(|_| Pubkey::new_unique()).collect();
+        for (i, tally) in tallies.iter().take(MAX_LEADERBOARD_TOP_N as usize).enumerate() {
+            leaderboard.rank(entry(*tally, 100 + i as u64));
+        }
+        assert_eq!(leaderboard.standings.len(), MAX_LEADERBOARD_TOP_N as usize);
+        assert_eq!(leaderboard.standings[0].saved_liters, 109);
+        assert_eq!(leaderboard.standings[9].tally, tallies[0]);
+
+        // A tie with the last place doesn't displace it
+        let newcomer = tallies[MAX_LEADERBOARD_TOP_N as usize];
+        leaderboard.rank(entry(newcomer, 100));
+        assert!(leaderboard.standings.iter().all(|e| e.tally != newcomer));
+
+        // ...a larger total does
+        leaderboard.rank(entry(newcomer, 105));
+        assert_eq!(leaderboard.standings.len(), MAX_LEADERBOARD_TOP_N as usize);
+        assert!(leaderboard.standings.iter().all(|e| e.tally != tallies[0]));
+        assert_eq!(leaderboard.standings[4].tally, tallies[5]); // reached 105 first
+        assert_eq!(leaderboard.standings[5].tally, newcomer);
+    }
+
+    #[test]
+    fn rank_moves_an_existing_entry_instead_of_duplicating_it() {
+        let mut leaderboard: Leaderboard = zeroed();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        leaderboard.rank(entry(a, 10));
+        leaderboard.rank(entry(b, 20));
+        leaderboard.rank(entry(a, 30));
+        let order: Vec<(Pubkey, u64)> =
+            leaderboard.standings.iter().map(|e| (e.tally, e.saved_liters)).collect();
+        assert_eq!(order, vec![(a, 30), (b, 20)]);
+    }
+
+    #[test]
+    fn issuance_amount_scales_by_rate_and_decimals() {
+        let mut remainder = 0;
+        assert_eq!(issuance_amount(1_500, 10_000, 6, &mut remainder).unwrap(), 1_500_000);
+        assert_eq!(issuance_amount(1_500, 5_000, 6, &mut remainder).unwrap(), 750_000);
+        assert_eq!(issuance_amount(1_000, 10_000, 0, &mut remainder).unwrap(), 1);
+        assert_eq!(remainder, 0);
+    }
+
+    #[test]
+    fn issuance_amount_carries_fractions_to_the_next_mint() {
+        let mut remainder = 0;
+        assert_eq!(issuance_amount(1_500, 10_000, 0, &mut remainder).unwrap(), 1);
+        assert_eq!(remainder, 500 * BPS_DENOMINATOR);
+        assert_eq!(issuance_amount(400, 10_000, 0, &mut remainder).unwrap(), 0);
+        assert_eq!(issuance_amount(100, 10_000, 0, &mut remainder).unwrap(), 1);
+        assert_eq!(remainder, 0);
+    }
+
+    #[test]
+    fn issuance_amount_reports_overflow() {
+        let mut remainder = 0;
+        assert!(issuance_amount(u64::MAX, u32::MAX, 18, &mut remainder).is_err());
+        assert!(issuance_amount(1, 10_000, 40, &mut remainder).is_err());
+        assert!(issuance_amount(u64::MAX, 10_000, 19, &mut remainder).is_err());
+    }
+
+    #[test]
+    fn to_liters_converts_each_unit() {
+        assert_eq!(VolumeUnit::Liters.to_liters(42).unwrap(), 42);
+        assert_eq!(VolumeUnit::CubicMeters.to_liters(3).unwrap(), 3_000);
+        assert_eq!(VolumeUnit::Gallons.to_liters(10).unwrap(), 37); // 37.85 rounds down
+        assert_eq!(VolumeUnit::Gallons.to_liters(1_000).unwrap(), 3_785);
+        assert!(VolumeUnit::CubicMeters.to_liters(u64::MAX).is_err());
+        assert!(VolumeUnit::Gallons.to_liters(u64::MAX).is_err());
+    }
+
+    fn rules() -> PlausibilityRules {
+        let mut rules: PlausibilityRules = zeroed();
+        rules.min_interval_secs = 60;
+        rules.max_flow_lph = 1_000;
+        rules.max_z_score_x100 = 300;
+        rules.min_samples = 3;
+        rules.ewma_weight_bps = 2_000;
+        rules
+    }
+
+    #[test]
+    fn check_passes_first_readings_and_plausible_flow() {
+        let rules = rules();
+        let mut meter = meter_at(0, 0);
+        meter.reading_count = 0;
+        assert_eq!(rules.check(&meter, u64::MAX, 0), None);
+
+        let meter = meter_at(1_000, 0);
+        assert_eq!(rules.check(&meter, 1_500, 3_600), None);
+        assert_eq!(rules.check(&meter, 2_000, 3_600), None); // exactly at the limit
+    }
+
+    #[test]
+    fn check_flags_readings_too_soon_or_too_fast() {
+        let rules = rules();
+        let meter = meter_at(1_000, 0);
+        assert_eq!(rules.check(&meter, 1_001, 59), Some(AnomalyKind::TooFrequent));
+        assert_eq!(rules.check(&meter, 2_001, 3_600), Some(AnomalyKind::FlowTooHigh));
+
+        let disabled = PlausibilityRules { max_flow_lph: 0, ..rules };
+        assert_eq!(disabled.check(&meter, 2_001, 3_600), None);
+    }
+
+    #[test]
+    fn check_flags_z_score_outliers_once_enough_samples_exist() {
+        let rules = PlausibilityRules { max_flow_lph: 0, ..rules() };
+        let mut meter = meter_at(0, 0);
+        meter.flow_mean_lpd = 1_000;
+        meter.flow_var = 100 * 100; // sd of 100 L/day
+        meter.flow_samples = 2;
+        assert_eq!(rules.check(&meter, 2_000, SECONDS_PER_DAY), None);
+
+        meter.flow_samples = 3;
+        assert_eq!(rules.check(&meter, 1_300, SECONDS_PER_DAY), None); // exactly 3 sd
+        assert_eq!(rules.check(&meter, 1_301, SECONDS_PER_DAY), Some(AnomalyKind::ZScore));
+        assert_eq!(rules.check(&meter, 699, SECONDS_PER_DAY), Some(AnomalyKind::ZScore));
+    }
+
+    // A 4-digit m³ register (10 million liters) last read at `register` m³
+    fn register_meter(register: u64) -> Meter {
+        let mut meter = meter_at(register * LITERS_PER_M3, 0);
+        meter.register_digits = 4;
+        meter.last_register = register * LITERS_PER_M3;
+        meter
+    }
+
+    #[test]
+    fn continuous_reading_applies_the_stored_offset() {
+        let mut meter = register_meter(10);
+        assert_eq!(meter.continuous_reading(12_000, VolumeUnit::CubicMeters).unwrap(), (12_000, 0));
+
+        meter.register_offset = 50_000;
+        assert_eq!(
+            meter.continuous_reading(12_000, VolumeUnit::CubicMeters).unwrap(),
+            (62_000, 50_000)
+        );
+
+        // A replacement that starts above the old total carries a negative offset
+        meter.register_offset = -2_000;
+        assert_eq!(
+            meter.continuous_reading(12_000, VolumeUnit::CubicMeters).unwrap(),
+            (10_000, -2_000)
+        );
+    }
+
+    #[test]
+    fn continuous_reading_wraps_from_the_top_of_the_register() {
+        let meter = register_meter(9_950);
+        assert_eq!(
+            meter.continuous_reading(20_000, VolumeUnit::CubicMeters).unwrap(),
+            (10_020_000, 10_000_000)
+        );
+
+        let meter = register_meter(9_000); // lowest value still inside the wrap window
+        assert!(meter.continuous_reading(0, VolumeUnit::CubicMeters).is_ok());
+    }
+
+    #[test]
+    fn continuous_reading_rejects_drops_that_cannot_be_a_wrap() {
+        let meter = register_meter(8_999);
+        assert!(meter.continuous_reading(20_000, VolumeUnit::CubicMeters).is_err());
+
+        // Without a register size every drop passes through for the caller to reject
+        let mut meter = register_meter(8_999);
+        meter.register_digits = 0;
+        assert_eq!(meter.continuous_reading(20_000, VolumeUnit::CubicMeters).unwrap(), (20_000, 0));
+    }
+
+    #[test]
+    fn tally_forfeit_only_touches_the_open_period() {
+        let beneficiary = Pubkey::new_unique();
+        let mut tally: SavingsTally = zeroed();
+        tally.record(4, 100, beneficiary);
+        assert!(!tally.forfeit(3, 40));
+        assert!(tally.forfeit(4, 40));
+        assert_eq!(tally.saved_liters, 60);
+
+        // Fully forfeited savings still leave the period's streak in place
+        assert!(tally.forfeit(4, 100));
+        assert_eq!(tally.saved_liters, 0);
+        assert!(!tally.record(4, 10, beneficiary));
+        assert_eq!((tally.saved_liters, tally.streak), (10, 1));
+    }
+
+    #[test]
+    fn lower_reorders_or_drops_a_standing() {
+        let mut leaderboard: Leaderboard = zeroed();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        leaderboard.rank(entry(a, 30));
+        leaderboard.rank(entry(b, 20));
+
+        leaderboard.lower(a, 10);
+        let order: Vec<Pubkey> = leaderboard.standings.iter().map(|e| e.tally).collect();
+        assert_eq!(order, vec![b, a]);
+
+        leaderboard.lower(b, 0);
+        let order: Vec<Pubkey> = leaderboard.standings.iter().map(|e| e.tally).collect();
+        assert_eq!(order, vec![a]);
+    }
+
+    #[test]
+    fn closed_reclamation_period_waits_for_its_report() {
+        let mut facility: Facility = zeroed();
+        facility.period_secs = 100;
+        facility.open_period.period = 3;
+
+        // Readings still in period 3 accrue there, however late the crank runs
+        assert!(facility.attribute(3, 500).is_none());
+        assert_eq!(facility.open_period.recycled_liters, 500);
+
+        // The first reading in period 4 closes 3 without forfeiting it
+        assert!(facility.attribute(4, 200).is_none());
+        let closed = facility.closed_period.unwrap();
+        assert_eq!((closed.period, closed.recycled_liters), (3, 500));
+        assert_eq!((facility.open_period.period, facility.open_period.recycled_liters), (4, 200));
+
+        // Still unreported when 4 closes: only then is 3 forfeited
+        let stale = facility.attribute(5, 0).unwrap();
+        assert_eq!((stale.period, stale.recycled_liters), (3, 500));
+        assert_eq!(facility.closed_period.unwrap().period, 4);
+        assert_eq!(facility.period_start(4), 400);
+    }
+
+    #[test]
+    fn district_loss_without_supply_credits_nothing() {
+        let loss = district_loss(0, 500, 2_000, 100);
+        assert_eq!((loss.billed, loss.loss_bps, loss.credited), (0, 0, 0));
+    }
+
+    #[test]
+    fn district_loss_caps_billing_at_supply() {
+        // Customers read more than went in: no loss, but only supply counts as billed
+        let loss = district_loss(10_000, 12_000, 2_000, 100);
+        assert_eq!((loss.billed, loss.loss_bps, loss.credited), (10_000, 0, 2_000));
+    }
+
+    #[test]
+    fn district_loss_above_baseline_credits_nothing() {
+        let loss = district_loss(10_000, 7_000, 2_000, 150);
+        assert_eq!((loss.billed, loss.loss_bps, loss.credited), (7_000, 3_000, 0));
+    }
+
+    #[test]
+    fn district_loss_below_baseline_credits_avoided_liters() {
+        // 10% lost against a 20% baseline: 1,000 L avoided, times 1.5
+        let loss = district_loss(10_000, 9_000, 2_000, 150);
+        assert_eq!((loss.billed, loss.loss_bps, loss.credited), (9_000, 1_000, 1_500));
+    }
+}
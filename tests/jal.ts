@@ -1,16 +1,91 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { createAccount } from "@solana/spl-token";
+import { assert } from "chai";
 import { Jal } from "../target/types/jal";
 
 describe("jal", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.jal as Program<Jal>;
 
+  const [configPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [mintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("mint")],
+    program.programId
+  );
+
   it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
+    const tx = await program.methods
+      .initialize(new anchor.BN(1000), 120, 10_000, 6, null)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
     console.log("Your transaction signature", tx);
+
+    const config = await program.account.mintConfig.fetch(configPda);
+    assert.ok(config.admin.equals(provider.wallet.publicKey));
+    assert.ok(config.mint.equals(mintPda));
+  });
+
+  it("Refuses to initialize twice", async () => {
+    try {
+      await program.methods
+        .initialize(new anchor.BN(1000), 120, 10_000, 6, null)
+        .accounts({ admin: provider.wallet.publicKey })
+        .rpc();
+      assert.fail("second initialize should fail");
+    } catch (err) {
+      assert.notInclude(String(err), "second initialize should fail");
+    }
+  });
+
+  it("Grants roles from the super-admin", async () => {
+    await program.methods
+      .grantRole({ meterRegistrar: {} }, provider.wallet.publicKey)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+
+    const [rolePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("role"), Buffer.from([3]), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const assignment = await program.account.roleAssignment.fetch(rolePda);
+    assert.ok(assignment.holder.equals(provider.wallet.publicKey));
+  });
+
+  it("Registers a meter with a fixed type and beneficiary", async () => {
+    const meterId = "LKV-A-101";
+    const beneficiary = await createAccount(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      mintPda,
+      provider.wallet.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+
+    await program.methods
+      .registerMeter(
+        meterId,
+        { residential: {} },
+        anchor.web3.PublicKey.default,
+        1,
+        new anchor.BN(Math.floor(Date.now() / 1000) - 60)
+      )
+      .accounts({ beneficiary, authority: provider.wallet.publicKey })
+      .rpc();
+
+    const [meterPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("meter"), Buffer.from(meterId)],
+      program.programId
+    );
+    const meter = await program.account.meter.fetch(meterPda);
+    assert.ok(meter.beneficiary.equals(beneficiary));
+    assert.deepEqual(meter.meterType, { residential: {} });
+    assert.deepEqual(meter.status, { active: {} });
   });
 });